@@ -2,7 +2,54 @@ pub const SEED_PROTOCOL: &[u8] = b"protocol_v1";
 pub const SEED_POOL: &[u8] = b"pool";
 pub const SEED_POOL_VAULT: &[u8] = b"pool_vault";
 pub const SEED_BET: &[u8] = b"bet";
+pub const SEED_FEE_VAULT: &[u8] = b"fee_vault";
+pub const SEED_FEE_LEDGER: &[u8] = b"fee_ledger";
+pub const SEED_CONFIG_CHANGE: &[u8] = b"config_change";
+pub const SEED_GOVERNANCE: &[u8] = b"governance";
+pub const SEED_PROPOSAL: &[u8] = b"proposal";
+pub const SEED_BOND_VAULT: &[u8] = b"bond_vault";
+pub const SEED_CREATOR_WHITELIST: &[u8] = b"creator_whitelist";
+pub const SEED_SPONSOR_REWARD: &[u8] = b"sponsor_reward";
+pub const SEED_SPONSOR_VAULT: &[u8] = b"sponsor_vault";
+pub const SEED_SPONSOR_CLAIM: &[u8] = b"sponsor_claim";
+pub const SEED_REFERRER: &[u8] = b"referrer";
+pub const SEED_DEPOSIT_POOL: &[u8] = b"deposit_pool";
+pub const SEED_DEPOSIT_VAULT: &[u8] = b"deposit_vault";
+pub const SEED_SHIELDED_BALANCE: &[u8] = b"shielded_balance";
+pub const SEED_POOL_AGGREGATES: &[u8] = b"pool_aggregates";
+pub const SEED_APPROVED_VALIDATOR: &[u8] = b"approved_validator";
 
 pub const MAX_STRATEGY_LENGTH: usize = 32;
+pub const MAX_POOL_TITLE_LEN: usize = 64;
 pub const MERCY_BUFFER_DEFAULT: u64 = 500; 
-pub const DISCRIMINATOR_SIZE: usize = 8;
\ No newline at end of file
+pub const DISCRIMINATOR_SIZE: usize = 8;
+
+pub const PAUSE_BET_INTAKE: u8 = 1 << 0;
+pub const PAUSE_UPDATES: u8 = 1 << 1;
+pub const PAUSE_SETTLEMENT: u8 = 1 << 2;
+pub const PAUSE_CLAIMS: u8 = 1 << 3;
+pub const PAUSE_POOL_CREATION: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 = PAUSE_BET_INTAKE
+    | PAUSE_UPDATES
+    | PAUSE_SETTLEMENT
+    | PAUSE_CLAIMS
+    | PAUSE_POOL_CREATION;
+
+pub const MAX_GOVERNANCE_SIGNERS: usize = 10;
+/// Proposals not executed this long after creation can no longer be approved or executed.
+pub const PROPOSAL_EXPIRY_SECONDS: i64 = 7 * 24 * 60 * 60;
+/// Extra viewers a bettor may add to a bet's permission, besides themselves
+/// and the compliance viewer.
+pub const MAX_BET_VIEWERS: usize = 4;
+/// Floor for `config_timelock`, so the admin it constrains cannot opt out of it.
+pub const MIN_CONFIG_TIMELOCK: i64 = 24 * 60 * 60;
+pub const MAX_CONFIG_TIMELOCK: i64 = 30 * 24 * 60 * 60;
+/// Bets can be refunded this long after their end time if the pool was never resolved.
+pub const REFUND_TIMEOUT_SECONDS: i64 = 60;
+/// A pool still unresolved this long after `end_time` forfeits its bond, and
+/// its creator may take back the seed subsidy.
+pub const BOND_RESOLUTION_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_PROTOCOL_FEE_BPS: u64 = 1_000;
+pub const MAX_FEE_SHARE_BPS: u64 = 5_000;
\ No newline at end of file
@@ -34,4 +34,102 @@ pub enum CustomError {
     NotCalculatedYet,
     #[msg("You must wait for the pool to end before undelegating to preserve privacy.")]
     UndelegationTooEarly,
+    #[msg("A token account is required for this pool.")]
+    MissingTokenAccount,
+    #[msg("Withdrawal exceeds the fees available for this mint.")]
+    InsufficientFees,
+    #[msg("Fee shares exceed the allowed maximum.")]
+    FeeShareTooHigh,
+    #[msg("A bet cannot refer itself.")]
+    InvalidReferrer,
+    #[msg("Protocol fee exceeds the allowed maximum.")]
+    FeeTooHigh,
+    #[msg("There is no pending admin transfer.")]
+    NoPendingAdmin,
+    #[msg("The pending admin proposal has expired.")]
+    AdminProposalExpired,
+    #[msg("This pool is frozen.")]
+    PoolFrozen,
+    #[msg("Unknown pause flags.")]
+    InvalidPauseFlags,
+    #[msg("The config change is not yet effective.")]
+    TimelockNotElapsed,
+    #[msg("Duration is out of the allowed range.")]
+    InvalidDuration,
+    #[msg("This pool has been cancelled.")]
+    PoolCancelled,
+    #[msg("Invalid governance signer set or threshold.")]
+    InvalidGovernanceConfig,
+    #[msg("Signer is not a governance member.")]
+    NotGovernanceSigner,
+    #[msg("Proposal already approved by this signer.")]
+    AlreadyApproved,
+    #[msg("Proposal has already been executed.")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal does not have enough approvals.")]
+    ThresholdNotMet,
+    #[msg("Pool title is too long.")]
+    TitleTooLong,
+    #[msg("Account is already at the current version.")]
+    AlreadyMigrated,
+    #[msg("Pool creation is restricted to the pool creator role.")]
+    PoolCreationClosed,
+    #[msg("Creator is not on the whitelist.")]
+    CreatorNotWhitelisted,
+    #[msg("Creator bond vault is missing or invalid.")]
+    MissingBondVault,
+    #[msg("Creator bond cannot be settled yet.")]
+    BondNotSettleable,
+    #[msg("Creator bond has already been settled.")]
+    BondAlreadySettled,
+    #[msg("Seed subsidy cannot be reclaimed.")]
+    SubsidyNotReclaimable,
+    #[msg("Sponsor rewards can no longer be added to this pool.")]
+    SponsorshipClosed,
+    #[msg("Sponsor reward cannot be refunded.")]
+    SponsorRewardNotRefundable,
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Referrer account is missing or does not match the bet.")]
+    MissingReferrerAccount,
+    #[msg("No referral rewards to claim.")]
+    NoReferralRewards,
+    #[msg("Denomination must be greater than zero.")]
+    InvalidDenomination,
+    #[msg("Insufficient shielded balance.")]
+    InsufficientShieldedBalance,
+    #[msg("Shielded stakes for this pool have not been settled.")]
+    ShieldedStakeUnsettled,
+    #[msg("Missing or invalid ed25519 signature instruction.")]
+    InvalidSignatureInstruction,
+    #[msg("Bet stake can no longer be changed.")]
+    StakeLocked,
+    #[msg("Pool aggregates have not been synced to L1.")]
+    AggregatesNotSynced,
+    #[msg("Pool aggregates account is missing.")]
+    MissingPoolAggregates,
+    #[msg("Permission account does not match the bet.")]
+    InvalidPermissionAccount,
+    #[msg("Bet permission already has the maximum number of viewers.")]
+    TooManyViewers,
+    #[msg("Viewer cannot be added or removed.")]
+    InvalidViewer,
+    #[msg("Bet has not been claimed yet.")]
+    BetNotClaimed,
+    #[msg("Validator is not the one pinned for this pool.")]
+    ValidatorNotPinned,
+    #[msg("Betting window is not open.")]
+    BettingWindowClosed,
+    #[msg("Shielded amounts must be whole notes of the deposit denomination.")]
+    PartialNote,
+    #[msg("Permission is still delegated to the rollup.")]
+    PermissionStillDelegated,
+    #[msg("Pool validator can no longer be changed.")]
+    PoolValidatorLocked,
+    #[msg("Pool has already been resolved.")]
+    PoolAlreadyResolved,
+    #[msg("Bet has no winning weight.")]
+    NoWinningWeight,
+    #[msg("Proposal has expired.")]
+    ProposalExpired,
 }
\ No newline at end of file
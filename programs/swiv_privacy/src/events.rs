@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{ConfigParam, GovernanceAction, Role};
 
 #[event]
 pub struct ProtocolInitialized {
@@ -12,12 +13,64 @@ pub struct ConfigUpdated {
     pub protocol_fee_bps: Option<u64>,
 }
 
+#[event]
+pub struct ConfigChangeQueued {
+    pub id: u64,
+    pub change: ConfigParam,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub id: u64,
+    pub change: ConfigParam,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub id: u64,
+}
+
+#[event]
+pub struct GovernanceUpdated {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub action: GovernanceAction,
+}
+
+#[event]
+pub struct FeeSplitUpdated {
+    pub creator_fee_share_bps: u64,
+    pub referrer_fee_share_bps: u64,
+}
+
 #[event]
 pub struct BetPlaced {
     pub bet_address: Pubkey,
     pub user: Pubkey,
     pub pool_identifier: String,
-    pub amount: u64,
+    /// `None` while the pool's privacy policy hides stakes.
+    pub amount: Option<u64>,
     pub end_timestamp: i64,
 }
 
@@ -33,6 +86,10 @@ pub struct PoolCreated {
     pub pool_name: String,
     pub start_time: i64,
     pub end_time: i64,
+    pub fee_bps: u64,
+    pub creator: Pubkey,
+    pub creator_bond: u64,
+    pub seed_subsidy: u64,
 }
 
 #[event]
@@ -58,6 +115,13 @@ pub struct PoolResolved {
     pub resolution_ts: i64,
 }
 
+#[event]
+pub struct PoolCancelled {
+    pub pool_address: Pubkey,
+    pub cancelled_at: i64,
+    pub creator_fault: bool,
+}
+
 #[event]
 pub struct AssetConfigUpdated {
     pub symbol: String,
@@ -70,6 +134,36 @@ pub struct PauseChanged {
     pub is_paused: bool,
 }
 
+#[event]
+pub struct PauseFlagsChanged {
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct PoolFreezeChanged {
+    pub pool_address: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct RoleUpdated {
+    pub role: Role,
+    pub previous: Pubkey,
+    pub member: Pubkey,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AdminProposalCancelled {
+    pub pending_admin: Pubkey,
+}
+
 #[event]
 pub struct AdminTransferred {
     pub old_admin: Pubkey,
@@ -88,6 +182,15 @@ pub struct WeightsFinalized {
     pub pool_name: String,
     pub total_weight: u128,
     pub fee_deducted: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
@@ -102,12 +205,146 @@ pub struct RewardClaimed {
     pub bet_address: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct ReferralRewardCredited {
+    pub bet_address: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
 pub struct BetRefunded {
     pub bet_address: Pubkey,
     pub user: Pubkey,
-    pub amount: u64,
+    /// `None` while the pool's privacy policy hides stakes.
+    pub amount: Option<u64>,
     pub is_emergency: bool,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct PoolCreationPolicyUpdated {
+    pub open_pool_creation: bool,
+    pub creator_whitelist_enabled: bool,
+    pub creator_bond_amount: u64,
+}
+
+#[event]
+pub struct CreatorWhitelistUpdated {
+    pub creator: Pubkey,
+    pub whitelisted: bool,
+}
+
+#[event]
+pub struct CreatorBondSettled {
+    pub pool_address: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub slashed: bool,
+}
+
+#[event]
+pub struct SeedSubsidyReclaimed {
+    pub pool_address: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PoolSponsored {
+    pub pool_address: Pubkey,
+    pub sponsor: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SponsorRewardClaimed {
+    pub bet_address: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SponsorRewardRefunded {
+    pub pool_address: Pubkey,
+    pub sponsor: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DepositPoolInitialized {
+    pub mint: Pubkey,
+    pub denomination: u64,
+}
+
+#[event]
+pub struct ShieldedDeposit {
+    pub mint: Pubkey,
+    pub notes: u64,
+}
+
+#[event]
+pub struct ShieldedWithdrawal {
+    pub mint: Pubkey,
+    pub notes: u64,
+}
+
+#[event]
+pub struct ShieldedStakeSettled {
+    pub pool_address: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PoolAggregatesCreated {
+    pub pool_address: Pubkey,
+}
+
+#[event]
+pub struct PoolAggregatesSynced {
+    pub pool_address: Pubkey,
+    pub total_volume: u64,
+    pub total_participants: u64,
+}
+
+#[event]
+pub struct BetPermissionUndelegated {
+    pub bet_address: Pubkey,
+}
+
+#[event]
+pub struct BetPermissionClosed {
+    pub bet_address: Pubkey,
+    pub reclaimed: u64,
+}
+
+#[event]
+pub struct ValidatorApprovalUpdated {
+    pub validator: Pubkey,
+    pub approved: bool,
+}
+
+#[event]
+pub struct PoolValidatorPinned {
+    pub pool_address: Pubkey,
+    pub validator: Pubkey,
 }
\ No newline at end of file
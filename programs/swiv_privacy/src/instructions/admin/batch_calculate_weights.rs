@@ -1,7 +1,7 @@
-use crate::constants::SEED_POOL;
+use crate::constants::{SEED_POOL, SEED_PROTOCOL, PAUSE_SETTLEMENT};
 use crate::errors::CustomError;
 use crate::events::OutcomeCalculated;
-use crate::state::{BetStatus, Pool, Bet};
+use crate::state::{BetStatus, Pool, Bet, Protocol, Role};
 use crate::utils::math::{
     calculate_accuracy_score, calculate_conviction_bonus, calculate_time_bonus, calculate_weight,
 };
@@ -12,6 +12,14 @@ pub struct BatchCalculateWeights<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::CrankOperator, &admin.key()) @ CustomError::Unauthorized,
+        constraint = !protocol.is_paused(PAUSE_SETTLEMENT) @ CustomError::Paused
+    )]
+    pub protocol: Account<'info, Protocol>,
+
     #[account(
         mut,
         seeds = [
@@ -20,12 +28,17 @@ pub struct BatchCalculateWeights<'info> {
             &pool.pool_id.to_le_bytes()
         ],
         bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
     )]
     pub pool: Account<'info, Pool>,
 }
 
-pub fn batch_calculate_weights<'info>(
+/// Weighs the bets passed as remaining accounts. With `clear_predictions`
+/// each bet's raw prediction is zeroed once weighed, so only the weight is
+/// ever committed back to L1.
+fn calculate_weights<'info>(
     ctx: Context<'_, '_, '_, 'info, BatchCalculateWeights<'info>>,
+    clear_predictions: bool,
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let accounts_iter = &mut ctx.remaining_accounts.iter();
@@ -74,6 +87,9 @@ pub fn batch_calculate_weights<'info>(
         bet.calculated_weight = weight;
         bet.is_weight_added = true;
         bet.status = BetStatus::Resolved;
+        if clear_predictions {
+            bet.prediction = 0;
+        }
 
         let mut new_data: Vec<u8> = Vec::new();
         bet.try_serialize(&mut new_data)?;
@@ -87,4 +103,20 @@ pub fn batch_calculate_weights<'info>(
     }
 
     Ok(())
+}
+
+/// Runs on L1 against undelegated bets.
+pub fn batch_calculate_weights<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchCalculateWeights<'info>>,
+) -> Result<()> {
+    calculate_weights(ctx, false)
+}
+
+/// Runs inside the TEE rollup against the delegated pool and bets, before
+/// they are undelegated. Predictions are cleared as bets are weighed, so L1
+/// only learns `calculated_weight` and the pool's `total_weight`.
+pub fn calculate_weights_in_rollup<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchCalculateWeights<'info>>,
+) -> Result<()> {
+    calculate_weights(ctx, true)
 }
\ No newline at end of file
@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use crate::state::{ConfigChange, ConfigParam, Protocol, Role};
+use crate::constants::{
+    MAX_CONFIG_TIMELOCK, MAX_FEE_SHARE_BPS, MAX_PROTOCOL_FEE_BPS, MIN_CONFIG_TIMELOCK,
+    SEED_CONFIG_CHANGE, SEED_PROTOCOL,
+};
+use crate::errors::CustomError;
+use crate::events::{
+    ConfigChangeCancelled, ConfigChangeExecuted, ConfigChangeQueued, ConfigUpdated,
+    FeeSplitUpdated, PoolCreationPolicyUpdated, RoleUpdated,
+};
+use crate::instructions::admin::transfer_admin::set_pending_admin;
+
+#[derive(Accounts)]
+#[instruction(change: ConfigParam)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ConfigChange::LEN,
+        seeds = [SEED_CONFIG_CHANGE, &protocol.config_change_count.to_le_bytes()],
+        bump
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_config_change(ctx: Context<QueueConfigChange>, change: ConfigParam) -> Result<()> {
+    record_config_change(
+        &mut ctx.accounts.protocol,
+        &mut ctx.accounts.config_change,
+        ctx.accounts.admin.key(),
+        change,
+        ctx.bumps.config_change,
+    )
+}
+
+pub(crate) fn record_config_change(
+    protocol: &mut Protocol,
+    config_change: &mut ConfigChange,
+    proposer: Pubkey,
+    change: ConfigParam,
+    bump: u8,
+) -> Result<()> {
+    validate_config_param(&change)?;
+
+    // Protocols created before the floor existed may still hold a shorter delay.
+    let clock = Clock::get()?;
+    let effective_at = clock
+        .unix_timestamp
+        .checked_add(protocol.config_timelock.max(MIN_CONFIG_TIMELOCK))
+        .ok_or(CustomError::MathOverflow)?;
+
+    config_change.id = protocol.config_change_count;
+    config_change.proposer = proposer;
+    config_change.change = change.clone();
+    config_change.queued_at = clock.unix_timestamp;
+    config_change.effective_at = effective_at;
+    config_change.bump = bump;
+
+    protocol.config_change_count = protocol.config_change_count.checked_add(1).unwrap();
+
+    emit!(ConfigChangeQueued {
+        id: config_change.id,
+        change,
+        effective_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [SEED_CONFIG_CHANGE, &config_change.id.to_le_bytes()],
+        bump = config_change.bump,
+        has_one = proposer
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+
+    /// CHECK: Receives the rent of the executed change; checked by `has_one`.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+/// Permissionless once the timelock has elapsed: the change was already
+/// authorized when it was queued.
+pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let config_change = &ctx.accounts.config_change;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= config_change.effective_at,
+        CustomError::TimelockNotElapsed
+    );
+
+    apply_config_param(&mut ctx.accounts.protocol, &config_change.change)?;
+
+    emit!(ConfigChangeExecuted {
+        id: config_change.id,
+        change: config_change.change.clone(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::Pauser, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [SEED_CONFIG_CHANGE, &config_change.id.to_le_bytes()],
+        bump = config_change.bump,
+        has_one = proposer
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+
+    /// CHECK: Receives the rent of the cancelled change; checked by `has_one`.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+/// The pauser can veto a queued change, which keeps cancellation reachable
+/// once admin authority sits with the governance PDA.
+pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+    emit!(ConfigChangeCancelled {
+        id: ctx.accounts.config_change.id,
+    });
+
+    Ok(())
+}
+
+fn validate_config_param(change: &ConfigParam) -> Result<()> {
+    match change {
+        ConfigParam::ProtocolFeeBps(fee) => {
+            require!(*fee <= MAX_PROTOCOL_FEE_BPS, CustomError::FeeTooHigh);
+        }
+        ConfigParam::MaxPauseDuration(duration) => {
+            require!(*duration >= 0, CustomError::InvalidDuration);
+        }
+        ConfigParam::ConfigTimelock(delay) => {
+            require!(
+                (MIN_CONFIG_TIMELOCK..=MAX_CONFIG_TIMELOCK).contains(delay),
+                CustomError::InvalidDuration
+            );
+        }
+        ConfigParam::FeeSplit {
+            creator_fee_share_bps,
+            referrer_fee_share_bps,
+        } => {
+            // Shares are expressed in bps of the protocol fee; the treasury keeps the rest.
+            let total_share = creator_fee_share_bps
+                .checked_add(*referrer_fee_share_bps)
+                .ok_or(CustomError::MathOverflow)?;
+            require!(total_share <= MAX_FEE_SHARE_BPS, CustomError::FeeShareTooHigh);
+        }
+        ConfigParam::Treasury(_)
+        | ConfigParam::Admin { .. }
+        | ConfigParam::ComplianceViewer(_)
+        | ConfigParam::Role { .. }
+        | ConfigParam::PoolCreationPolicy { .. } => {}
+    }
+    Ok(())
+}
+
+pub(crate) fn apply_config_param(protocol: &mut Protocol, change: &ConfigParam) -> Result<()> {
+    validate_config_param(change)?;
+
+    match change {
+        ConfigParam::ProtocolFeeBps(fee) => {
+            protocol.protocol_fee_bps = *fee;
+            emit!(ConfigUpdated {
+                treasury: None,
+                protocol_fee_bps: Some(*fee),
+            });
+        }
+        ConfigParam::Treasury(treasury) => {
+            protocol.treasury_wallet = *treasury;
+            emit!(ConfigUpdated {
+                treasury: Some(*treasury),
+                protocol_fee_bps: None,
+            });
+        }
+        ConfigParam::Admin {
+            new_admin,
+            valid_for_seconds,
+        } => set_pending_admin(protocol, *new_admin, *valid_for_seconds)?,
+        ConfigParam::MaxPauseDuration(duration) => protocol.max_pause_duration = *duration,
+        ConfigParam::ConfigTimelock(delay) => protocol.config_timelock = *delay,
+        ConfigParam::ComplianceViewer(viewer) => protocol.compliance_viewer = *viewer,
+        ConfigParam::FeeSplit {
+            creator_fee_share_bps,
+            referrer_fee_share_bps,
+        } => {
+            protocol.creator_fee_share_bps = *creator_fee_share_bps;
+            protocol.referrer_fee_share_bps = *referrer_fee_share_bps;
+            emit!(FeeSplitUpdated {
+                creator_fee_share_bps: *creator_fee_share_bps,
+                referrer_fee_share_bps: *referrer_fee_share_bps,
+            });
+        }
+        ConfigParam::Role { role, member } => {
+            // Assigning `Pubkey::default()` revokes the role.
+            let previous = protocol.role_holder(*role);
+            protocol.set_role_holder(*role, *member);
+            emit!(RoleUpdated {
+                role: *role,
+                previous,
+                member: *member,
+            });
+        }
+        ConfigParam::PoolCreationPolicy {
+            open_pool_creation,
+            creator_whitelist_enabled,
+            creator_bond_amount,
+        } => {
+            protocol.open_pool_creation = *open_pool_creation;
+            protocol.creator_whitelist_enabled = *creator_whitelist_enabled;
+            protocol.creator_bond_amount = *creator_bond_amount;
+            emit!(PoolCreationPolicyUpdated {
+                open_pool_creation: *open_pool_creation,
+                creator_whitelist_enabled: *creator_whitelist_enabled,
+                creator_bond_amount: *creator_bond_amount,
+            });
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::Protocol;
-use crate::constants::{SEED_PROTOCOL};
+use crate::state::{Protocol, Role};
+use crate::constants::{MAX_PROTOCOL_FEE_BPS, MIN_CONFIG_TIMELOCK, SEED_PROTOCOL};
+use crate::errors::CustomError;
 use crate::events::ProtocolInitialized;
 
 #[derive(Accounts)]
@@ -11,7 +12,7 @@ pub struct InitializeProtocol<'info> {
     #[account(
         init,
         payer = admin,
-        space = Protocol::BASE_LEN,
+        space = Protocol::LEN,
         seeds = [SEED_PROTOCOL],
         bump
     )]
@@ -30,16 +31,39 @@ pub fn initialize_protocol(
     ctx: Context<InitializeProtocol>,
     protocol_fee_bps: u64 
 ) -> Result<()> {
+    require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, CustomError::FeeTooHigh);
+
     let protocol = &mut ctx.accounts.protocol;
     
     protocol.admin = ctx.accounts.admin.key();
     protocol.treasury_wallet = ctx.accounts.treasury_wallet.key();
     
     protocol.protocol_fee_bps = protocol_fee_bps;
+    protocol.creator_fee_share_bps = 0;
+    protocol.referrer_fee_share_bps = 0;
+    protocol.pending_admin = None;
+    protocol.pending_admin_expiry = 0;
+
+    for role in [
+        Role::PoolCreator,
+        Role::Resolver,
+        Role::Pauser,
+        Role::FeeManager,
+        Role::CrankOperator,
+        Role::ValidatorManager,
+    ] {
+        protocol.set_role_holder(role, Pubkey::default());
+    }
     
     protocol.paused = false;
+    protocol.pause_flags = 0;
+    protocol.paused_at = 0;
+    protocol.max_pause_duration = 0;
+    protocol.config_timelock = MIN_CONFIG_TIMELOCK;
+    protocol.config_change_count = 0;
     protocol.total_pools = 0;
     protocol.batch_settle_wait_duration = 60; 
+    protocol.version = Protocol::CURRENT_VERSION;
 
     emit!(ProtocolInitialized {
         admin: ctx.accounts.admin.key(),
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::{Bet, FeeMode, Pool, PrivacyPolicy, Protocol, Role};
+use crate::constants::{DISCRIMINATOR_SIZE, MIN_CONFIG_TIMELOCK, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::AccountMigrated;
+
+/// Checks that `info` is a program-owned account of type `T` without
+/// deserializing it, since an outdated layout may not deserialize yet.
+fn check_account_type<T: Discriminator>(info: &AccountInfo) -> Result<()> {
+    if *info.owner != crate::ID {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let data = info.try_borrow_data()?;
+    if data.len() < DISCRIMINATOR_SIZE || data[..DISCRIMINATOR_SIZE] != *T::DISCRIMINATOR {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    Ok(())
+}
+
+/// Grows `info` to `len` bytes, topping up rent from `payer`. The new bytes
+/// are zeroed; fields whose default is not zero are set by `upgrade_account`.
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    len: usize,
+) -> Result<()> {
+    if info.data_len() >= len {
+        return Ok(());
+    }
+
+    let shortfall = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    info.resize(len)?;
+    Ok(())
+}
+
+/// Reallocates and re-serializes one account at the current layout version,
+/// filling in `defaults` for every version it skips.
+fn upgrade_account<'info, T>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    len: usize,
+    version: impl FnOnce(&mut T) -> &mut u8,
+    defaults: impl FnOnce(&mut T, u8),
+    current_version: u8,
+) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator,
+{
+    check_account_type::<T>(info)?;
+    grow_account(info, payer, system_program, len)?;
+
+    let mut data = info.try_borrow_mut_data()?;
+    let mut account = T::try_deserialize(&mut &data[..])?;
+    let slot = version(&mut account);
+    let from_version = *slot;
+    require!(from_version < current_version, CustomError::AlreadyMigrated);
+    *slot = current_version;
+    defaults(&mut account, from_version);
+
+    let mut writer: &mut [u8] = &mut data[..];
+    account.try_serialize(&mut writer)?;
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        from_version,
+        to_version: current_version,
+    });
+
+    Ok(())
+}
+
+fn protocol_defaults(protocol: &mut Protocol, from_version: u8) {
+    if from_version < 1 {
+        protocol.config_timelock = protocol.config_timelock.max(MIN_CONFIG_TIMELOCK);
+    }
+    if from_version < 2 {
+        protocol.open_pool_creation = false;
+        protocol.creator_whitelist_enabled = false;
+        protocol.creator_bond_amount = 0;
+    }
+    if from_version < 3 {
+        protocol.compliance_viewer = Pubkey::default();
+    }
+    if from_version < 4 {
+        protocol.validator_manager = Pubkey::default();
+    }
+}
+
+/// Unversioned pools were charged the protocol fee at claim time and had no
+/// fee snapshot, so they keep paying the protocol fee in effect.
+fn pool_defaults(pool: &mut Pool, from_version: u8, protocol_fee_bps: u64) {
+    if from_version < 1 {
+        if pool.fee_bps == 0 {
+            pool.fee_bps = protocol_fee_bps;
+        }
+        pool.fee_mode = FeeMode::OnVolume;
+    }
+    if from_version < 2 {
+        // Pools created before bonds have none to settle.
+        pool.creator_bond = 0;
+        pool.bond_settled = true;
+        pool.cancelled_for_creator_fault = false;
+    }
+    if from_version < 3 {
+        pool.seed_subsidy = 0;
+        pool.subsidy_reclaimed = false;
+    }
+    if from_version < 4 {
+        pool.privacy = PrivacyPolicy::default();
+    }
+    if from_version < 5 {
+        pool.shielded_volume = 0;
+        pool.shielded_settled = false;
+    }
+    if from_version < 6 {
+        pool.private_aggregates = false;
+        pool.aggregates_synced = false;
+    }
+    if from_version < 7 {
+        pool.pinned_validator = Pubkey::default();
+    }
+}
+
+/// Bets from before the compliance viewer was recorded per bet are assumed to
+/// carry the protocol's viewer in effect at migration.
+fn bet_defaults(bet: &mut Bet, from_version: u8, compliance_viewer: Pubkey) {
+    if from_version < 2 {
+        bet.shielded_stake = 0;
+    }
+    if from_version < 3 {
+        bet.referral_credited = false;
+    }
+    if from_version < 4 {
+        bet.compliance_viewer = compliance_viewer;
+    }
+}
+
+#[derive(Accounts)]
+pub struct MigrateProtocol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Deserialized manually after the realloc; type and role are checked in the handler.
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump
+    )]
+    pub protocol: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_protocol(ctx: Context<MigrateProtocol>) -> Result<()> {
+    let info = ctx.accounts.protocol.to_account_info();
+    check_account_type::<Protocol>(&info)?;
+
+    // Roles sit behind variable-length fields, so the outdated layout is
+    // grown first and read back whole before checking the signer.
+    grow_account(&info, &ctx.accounts.admin, &ctx.accounts.system_program, Protocol::LEN)?;
+    {
+        let data = info.try_borrow_data()?;
+        let protocol = Protocol::try_deserialize(&mut &data[..])?;
+        require!(
+            protocol.has_role(Role::CrankOperator, &ctx.accounts.admin.key()),
+            CustomError::Unauthorized
+        );
+    }
+
+    upgrade_account::<Protocol>(
+        &info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        Protocol::LEN,
+        |protocol| &mut protocol.version,
+        protocol_defaults,
+        Protocol::CURRENT_VERSION,
+    )
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccounts<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::CrankOperator, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pools are passed as writable remaining accounts and must not be delegated.
+pub fn migrate_pools<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateAccounts<'info>>,
+) -> Result<()> {
+    let protocol_fee_bps = ctx.accounts.protocol.protocol_fee_bps;
+    for info in ctx.remaining_accounts.iter() {
+        upgrade_account::<Pool>(
+            info,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            Pool::LEN,
+            |pool| &mut pool.version,
+            |pool, from_version| pool_defaults(pool, from_version, protocol_fee_bps),
+            Pool::CURRENT_VERSION,
+        )?;
+    }
+    Ok(())
+}
+
+/// Bets are passed as writable remaining accounts and must not be delegated.
+pub fn migrate_bets<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateAccounts<'info>>,
+) -> Result<()> {
+    let compliance_viewer = ctx.accounts.protocol.compliance_viewer;
+    for info in ctx.remaining_accounts.iter() {
+        upgrade_account::<Bet>(
+            info,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            Bet::LEN,
+            |bet| &mut bet.version,
+            |bet, from_version| bet_defaults(bet, from_version, compliance_viewer),
+            Bet::CURRENT_VERSION,
+        )?;
+    }
+    Ok(())
+}
@@ -1,11 +1,21 @@
 pub mod initialize_protocol;
 pub mod set_pause;
 pub mod batch_calculate_weights;
-pub mod update_config;
 pub mod transfer_admin;
+pub mod withdraw_fees;
+pub mod set_pool_freeze;
+pub mod config_timelock;
+pub mod migrate;
+pub mod pool_creation;
+pub mod validators;
 
 pub use initialize_protocol::*;
 pub use set_pause::*;
 pub use batch_calculate_weights::*;
-pub use update_config::*;
-pub use transfer_admin::*;
\ No newline at end of file
+pub use transfer_admin::*;
+pub use withdraw_fees::*;
+pub use set_pool_freeze::*;
+pub use config_timelock::*;
+pub use migrate::*;
+pub use pool_creation::*;
+pub use validators::*;
\ No newline at end of file
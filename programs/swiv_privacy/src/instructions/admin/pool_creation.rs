@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::{Protocol, Role, WhitelistedCreator};
+use crate::constants::{SEED_CREATOR_WHITELIST, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::CreatorWhitelistUpdated;
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AddWhitelistedCreator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::PoolCreator, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = WhitelistedCreator::LEN,
+        seeds = [SEED_CREATOR_WHITELIST, creator.as_ref()],
+        bump
+    )]
+    pub creator_whitelist: Account<'info, WhitelistedCreator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_whitelisted_creator(ctx: Context<AddWhitelistedCreator>, creator: Pubkey) -> Result<()> {
+    let entry = &mut ctx.accounts.creator_whitelist;
+    entry.creator = creator;
+    entry.added_at = Clock::get()?.unix_timestamp;
+    entry.bump = ctx.bumps.creator_whitelist;
+
+    emit!(CreatorWhitelistUpdated {
+        creator,
+        whitelisted: true,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveWhitelistedCreator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::PoolCreator, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [SEED_CREATOR_WHITELIST, creator_whitelist.creator.as_ref()],
+        bump = creator_whitelist.bump
+    )]
+    pub creator_whitelist: Account<'info, WhitelistedCreator>,
+}
+
+pub fn remove_whitelisted_creator(ctx: Context<RemoveWhitelistedCreator>) -> Result<()> {
+    emit!(CreatorWhitelistUpdated {
+        creator: ctx.accounts.creator_whitelist.creator,
+        whitelisted: false,
+    });
+
+    Ok(())
+}
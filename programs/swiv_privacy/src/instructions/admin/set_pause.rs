@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::Protocol;
-use crate::constants::SEED_PROTOCOL;
+use crate::state::{Protocol, Role};
+use crate::constants::{PAUSE_ALL, SEED_PROTOCOL};
 use crate::errors::CustomError;
-use crate::events::PauseChanged;
+use crate::events::{PauseChanged, PauseFlagsChanged};
 
 #[derive(Accounts)]
 pub struct SetPause<'info> {
@@ -10,7 +10,7 @@ pub struct SetPause<'info> {
         mut,
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+        constraint = protocol.has_role(Role::Pauser, &admin.key()) @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
@@ -18,11 +18,49 @@ pub struct SetPause<'info> {
 }
 
 pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
-    ctx.accounts.protocol.paused = paused;
+    apply_pause(&mut ctx.accounts.protocol, paused)
+}
+
+pub(crate) fn apply_pause(protocol: &mut Protocol, paused: bool) -> Result<()> {
+    protocol.paused = paused;
+    if paused {
+        protocol.paused_at = Clock::get()?.unix_timestamp;
+    }
 
     emit!(PauseChanged {
         is_paused: paused,
     });
 
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::Pauser, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Replaces the granular pause switches with `pause_flags` (a mask of the
+/// `PAUSE_*` constants). The global `paused` switch is left untouched.
+pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+    require!(pause_flags & !PAUSE_ALL == 0, CustomError::InvalidPauseFlags);
+
+    let protocol = &mut ctx.accounts.protocol;
+    protocol.pause_flags = pause_flags;
+    if pause_flags != 0 {
+        protocol.paused_at = Clock::get()?.unix_timestamp;
+    }
+
+    emit!(PauseFlagsChanged {
+        pause_flags,
+    });
+
     Ok(())
 }
\ No newline at end of file
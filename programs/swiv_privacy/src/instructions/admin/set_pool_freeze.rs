@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Protocol, Role};
+use crate::constants::{SEED_POOL, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::PoolFreezeChanged;
+
+#[derive(Accounts)]
+pub struct SetPoolFreeze<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::Pauser, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Freezes a single market without touching the rest of the protocol.
+pub fn set_pool_freeze(ctx: Context<SetPoolFreeze>, frozen: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.frozen = frozen;
+
+    emit!(PoolFreezeChanged {
+        pool_address: pool.key(),
+        frozen,
+    });
+
+    Ok(())
+}
@@ -2,28 +2,74 @@ use anchor_lang::prelude::*;
 use crate::state::Protocol;
 use crate::constants::SEED_PROTOCOL;
 use crate::errors::CustomError;
-use crate::events::AdminTransferred;
+use crate::events::{AdminProposalCancelled, AdminProposed, AdminTransferred};
+
+/// Starts an admin handover once a queued `ConfigParam::Admin` change executes.
+/// Nothing changes until `new_admin` signs `accept_admin`, so a mistyped key can
+/// simply be cancelled or left to expire.
+pub(crate) fn set_pending_admin(
+    protocol: &mut Protocol,
+    new_admin: Pubkey,
+    valid_for_seconds: Option<i64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let expires_at = match valid_for_seconds {
+        Some(duration) => {
+            require!(duration > 0, CustomError::DurationTooShort);
+            clock.unix_timestamp.checked_add(duration).ok_or(CustomError::MathOverflow)?
+        }
+        None => 0,
+    };
+
+    protocol.pending_admin = Some(new_admin);
+    protocol.pending_admin_expiry = expires_at;
+
+    emit!(AdminProposed {
+        current_admin: protocol.admin,
+        pending_admin: new_admin,
+        expires_at,
+    });
+
+    Ok(())
+}
 
 #[derive(Accounts)]
-pub struct TransferAdmin<'info> {
+pub struct AcceptAdmin<'info> {
     #[account(mut)]
-    pub current_admin: Signer<'info>,
+    pub new_admin: Signer<'info>,
 
     #[account(
         mut,
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == current_admin.key() @ CustomError::Unauthorized
+        constraint = protocol.pending_admin.is_some() @ CustomError::NoPendingAdmin,
+        constraint = protocol.pending_admin == Some(new_admin.key()) @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 }
 
-pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
-    let protocol = &mut ctx.accounts.protocol;
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    apply_accept_admin(&mut ctx.accounts.protocol, ctx.accounts.new_admin.key())
+}
+
+pub(crate) fn apply_accept_admin(protocol: &mut Protocol, new_admin: Pubkey) -> Result<()> {
+    require!(protocol.pending_admin.is_some(), CustomError::NoPendingAdmin);
+    require!(protocol.pending_admin == Some(new_admin), CustomError::Unauthorized);
+
+    let clock = Clock::get()?;
+
+    require!(
+        protocol.pending_admin_expiry == 0 || clock.unix_timestamp <= protocol.pending_admin_expiry,
+        CustomError::AdminProposalExpired
+    );
+
     let old_admin = protocol.admin;
-    
+
     protocol.admin = new_admin;
-    
+    protocol.pending_admin = None;
+    protocol.pending_admin_expiry = 0;
+
     emit!(AdminTransferred {
         old_admin,
         new_admin,
@@ -32,4 +78,34 @@ pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<
     msg!("Admin transferred from {} to {}", old_admin, new_admin);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(mut)]
+    pub current_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == current_admin.key() @ CustomError::Unauthorized,
+        constraint = protocol.pending_admin.is_some() @ CustomError::NoPendingAdmin
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    apply_cancel_admin_transfer(&mut ctx.accounts.protocol)
+}
+
+pub(crate) fn apply_cancel_admin_transfer(protocol: &mut Protocol) -> Result<()> {
+    let pending_admin = protocol.pending_admin.take().ok_or(CustomError::NoPendingAdmin)?;
+    protocol.pending_admin_expiry = 0;
+
+    emit!(AdminProposalCancelled {
+        pending_admin,
+    });
+
+    Ok(())
+}
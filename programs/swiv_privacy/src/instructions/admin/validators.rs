@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use crate::state::{ApprovedValidator, Pool, Protocol, Role};
+use crate::constants::{SEED_APPROVED_VALIDATOR, SEED_POOL, SEED_POOL_AGGREGATES, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::{PoolValidatorPinned, ValidatorApprovalUpdated};
+
+#[derive(Accounts)]
+#[instruction(validator: Pubkey)]
+pub struct AddApprovedValidator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::ValidatorManager, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ApprovedValidator::LEN,
+        seeds = [SEED_APPROVED_VALIDATOR, validator.as_ref()],
+        bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_approved_validator(ctx: Context<AddApprovedValidator>, validator: Pubkey) -> Result<()> {
+    let entry = &mut ctx.accounts.approved_validator;
+    entry.validator = validator;
+    entry.added_at = Clock::get()?.unix_timestamp;
+    entry.bump = ctx.bumps.approved_validator;
+
+    emit!(ValidatorApprovalUpdated {
+        validator,
+        approved: true,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveApprovedValidator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::ValidatorManager, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [SEED_APPROVED_VALIDATOR, approved_validator.validator.as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+}
+
+/// Accounts already delegated to the validator stay there until undelegated.
+pub fn remove_approved_validator(ctx: Context<RemoveApprovedValidator>) -> Result<()> {
+    emit!(ValidatorApprovalUpdated {
+        validator: ctx.accounts.approved_validator.validator,
+        approved: false,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPoolValidator<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = pool.created_by == authority.key()
+            || protocol.has_role(Role::PoolCreator, &authority.key()) @ CustomError::Unauthorized
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Required unless the pin is being cleared.
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, approved_validator.validator.as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Option<Account<'info, ApprovedValidator>>,
+
+    /// CHECK: Required for pools with private aggregates, which must not be
+    /// delegated yet.
+    #[account(
+        seeds = [SEED_POOL_AGGREGATES, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_aggregates: Option<UncheckedAccount<'info>>,
+}
+
+/// Pins the pool, and every bet in it, to one approved validator. Passing no
+/// `approved_validator` clears the pin. Must run before `start_time`, while
+/// the pool and its aggregates are still on L1; bets cannot exist before then.
+pub fn set_pool_validator(ctx: Context<SetPoolValidator>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(
+        Clock::get()?.unix_timestamp < pool.start_time,
+        CustomError::PoolValidatorLocked
+    );
+    if pool.private_aggregates {
+        let pool_aggregates = ctx
+            .accounts
+            .pool_aggregates
+            .as_ref()
+            .ok_or(CustomError::MissingPoolAggregates)?;
+        require!(
+            *pool_aggregates.owner == crate::ID,
+            CustomError::PoolValidatorLocked
+        );
+    }
+
+    let validator = ctx
+        .accounts
+        .approved_validator
+        .as_ref()
+        .map(|entry| entry.validator)
+        .unwrap_or_default();
+
+    let pool = &mut ctx.accounts.pool;
+    pool.pinned_validator = validator;
+
+    emit!(PoolValidatorPinned {
+        pool_address: pool.key(),
+        validator,
+    });
+
+    Ok(())
+}
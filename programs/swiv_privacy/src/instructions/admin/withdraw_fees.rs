@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{FeeLedger, Protocol, Role};
+use crate::constants::{SEED_FEE_LEDGER, SEED_FEE_VAULT, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::FeesWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::FeeManager, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_LEDGER, fee_ledger.mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT, fee_ledger.mint.as_ref()],
+        bump,
+        token::authority = protocol,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = fee_ledger.mint,
+        token::authority = protocol.treasury_wallet,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+
+    require!(amount <= fee_ledger.available(), CustomError::InsufficientFees);
+
+    let bump = ctx.bumps.protocol;
+    let seeds = &[SEED_PROTOCOL, &[bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: ctx.accounts.protocol.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    fee_ledger.total_withdrawn = fee_ledger.total_withdrawn.checked_add(amount).unwrap();
+
+    emit!(FeesWithdrawn {
+        mint: fee_ledger.mint,
+        treasury: ctx.accounts.treasury_token_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
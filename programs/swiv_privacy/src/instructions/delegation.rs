@@ -1,63 +1,102 @@
 use anchor_lang::prelude::*;
-use crate::state::{Bet, Protocol, Pool}; 
-use crate::constants::{SEED_BET, SEED_POOL, SEED_PROTOCOL}; 
+use crate::state::{ApprovedValidator, Bet, Protocol, Pool, PoolAggregates, Role, ShieldedBalance}; 
+use crate::constants::{
+    SEED_APPROVED_VALIDATOR, SEED_BET, SEED_POOL, SEED_POOL_AGGREGATES, SEED_PROTOCOL,
+    SEED_SHIELDED_BALANCE,
+}; 
 use crate::errors::CustomError;
 use crate::events::{
     PoolDelegated, PoolUndelegated, 
-    BetDelegated, BetUndelegated
+    BetDelegated, BetUndelegated, BetPermissionUndelegated
 }; 
-use ephemeral_rollups_sdk::access_control::instructions::DelegatePermissionCpiBuilder;
+use ephemeral_rollups_sdk::access_control::instructions::{
+    CommitAndUndelegatePermissionCpiBuilder, DelegatePermissionCpiBuilder,
+};
+use ephemeral_rollups_sdk::access_control::structs::Permission;
+use ephemeral_rollups_sdk::consts::PERMISSION_PROGRAM_ID;
 
 use ephemeral_rollups_sdk::anchor::{delegate, commit};
 use ephemeral_rollups_sdk::cpi::DelegateConfig;
 use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
 
+/// Rejects validators other than the one the pool is pinned to, if any. The
+/// pool is read directly since it may already be delegated.
+fn check_pool_validator(pool: &AccountInfo, validator: &Pubkey) -> Result<()> {
+    let pool_data = pool.try_borrow_data()?;
+    let pool = Pool::try_deserialize(&mut &pool_data[..])?;
+    require!(pool.accepts_validator(validator), CustomError::ValidatorNotPinned);
+    Ok(())
+}
+
 #[delegate]
 #[derive(Accounts)]
-#[instruction(pool_id: u64)]
 pub struct DelegatePool<'info> {
+    /// The crank operator or the pool's creator.
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
-    /// CHECK: The main pool account.
-   #[account(
-        mut, 
-        del, 
-        seeds = [SEED_POOL, admin.key().as_ref(), &pool_id.to_le_bytes()],
-        bump
-    )]
+    /// CHECK: The main pool account, checked against its stored seeds in the handler.
+    #[account(mut, del, owner = crate::ID)]
     pub pool: AccountInfo<'info>,
 
     /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
     pub validator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
 }
 
-pub fn delegate_pool(ctx: Context<DelegatePool>, pool_id: u64) -> Result<()> {
-    let admin_key = ctx.accounts.admin.key();
-    let admin_bytes = admin_key.as_ref();
-    let pool_id_bytes = pool_id.to_le_bytes();
+/// Delegates any pool from its stored `created_by` and `pool_id`, so it does
+/// not matter who created it or whether the admin key has since rotated.
+pub fn delegate_pool(ctx: Context<DelegatePool>) -> Result<()> {
+    let pool = {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        Pool::try_deserialize(&mut &pool_data[..])?
+    };
+
+    let authority = ctx.accounts.authority.key();
+    require!(
+        pool.created_by == authority
+            || ctx.accounts.protocol.has_role(Role::CrankOperator, &authority),
+        CustomError::Unauthorized
+    );
+    require!(
+        pool.accepts_validator(&ctx.accounts.validator.key()),
+        CustomError::ValidatorNotPinned
+    );
+
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
     let seeds = &[
         SEED_POOL,
-        admin_bytes,
+        pool.created_by.as_ref(),
         &pool_id_bytes,
     ];
 
+    let derived_pda = Pubkey::create_program_address(
+        &[SEED_POOL, pool.created_by.as_ref(), &pool_id_bytes, &[pool.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| CustomError::SeedMismatch)?;
+    require!(derived_pda == ctx.accounts.pool.key(), CustomError::SeedMismatch);
+
     let config = DelegateConfig {
         validator: Some(ctx.accounts.validator.key()),
         ..DelegateConfig::default()
     };
 
     ctx.accounts.delegate_pool(
-        &ctx.accounts.admin, 
-        seeds, 
-        config,             
+        &ctx.accounts.authority,
+        seeds,
+        config,
     )?;
 
     emit!(PoolDelegated {
@@ -106,6 +145,12 @@ pub struct DelegateBetPermission<'info> {
     /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
     pub validator: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -119,6 +164,7 @@ pub fn delegate_bet_permission(ctx: Context<DelegateBetPermission>, request_id:
 
     require!(owner == ctx.accounts.user.key(), CustomError::Unauthorized);
     require!(pool_pubkey == ctx.accounts.pool.key(), CustomError::PoolMismatch);
+    check_pool_validator(&ctx.accounts.pool, &ctx.accounts.validator.key())?;
 
     let pool_key = ctx.accounts.pool.key();
     let user_key = ctx.accounts.user.key();
@@ -165,6 +211,12 @@ pub struct DelegateBet<'info> {
 
     /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
     pub validator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
 }
 
 pub fn delegate_bet(ctx: Context<DelegateBet>, request_id: String) -> Result<()> {
@@ -177,6 +229,7 @@ pub fn delegate_bet(ctx: Context<DelegateBet>, request_id: String) -> Result<()>
 
     require!(owner == ctx.accounts.user.key(), CustomError::Unauthorized);
     require!(pool_pubkey == ctx.accounts.pool.key(), CustomError::PoolMismatch);
+    check_pool_validator(&ctx.accounts.pool, &ctx.accounts.validator.key())?;
 
     let pool_key = ctx.accounts.pool.key();
     let user_key = ctx.accounts.user.key();
@@ -212,25 +265,43 @@ pub fn delegate_bet(ctx: Context<DelegateBet>, request_id: String) -> Result<()>
 #[commit]
 #[derive(Accounts)]
 pub struct UndelegatePool<'info> {
+    /// The crank operator or the pool's creator.
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
-    
-    /// CHECK: The Pool account
-    #[account(mut)]
-    pub pool: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = pool.created_by == authority.key()
+            || protocol.has_role(Role::CrankOperator, &authority.key()) @ CustomError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
 }
 
+/// The crank may undelegate at any time; the creator only once betting has
+/// closed or the pool is resolved or cancelled.
 pub fn undelegate_pool(ctx: Context<UndelegatePool>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let authority = ctx.accounts.authority.key();
+    if !ctx.accounts.protocol.has_role(Role::CrankOperator, &authority) {
+        require!(
+            Clock::get()?.unix_timestamp >= pool.end_time
+                || pool.is_resolved
+                || pool.is_cancelled,
+            CustomError::UndelegationTooEarly
+        );
+    }
+
     commit_and_undelegate_accounts(
-        &ctx.accounts.admin,
-        vec![&ctx.accounts.pool],
+        &ctx.accounts.authority,
+        vec![&ctx.accounts.pool.to_account_info()],
         &ctx.accounts.magic_context,
         &ctx.accounts.magic_program,
     )?;
@@ -265,6 +336,15 @@ pub fn batch_undelegate_bets<'info>(ctx: Context<'_, '_, '_, 'info, BatchUndeleg
         CustomError::UndelegationTooEarly
     );
     
+    // Only this pool's bets may be flushed, so the crank cannot pull aggregates,
+    // balances or bets of pools that are still open back to L1.
+    for acc in ctx.remaining_accounts.iter() {
+        require!(*acc.owner == crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let bet_data = acc.try_borrow_data()?;
+        let bet = Bet::try_deserialize(&mut &bet_data[..])?;
+        require!(bet.pool_pubkey == pool.key(), CustomError::PoolMismatch);
+    }
+
     let accounts_to_undelegate: Vec<&AccountInfo<'info>> = ctx.remaining_accounts.iter().collect();
     
     if accounts_to_undelegate.is_empty() {
@@ -288,4 +368,438 @@ pub fn batch_undelegate_bets<'info>(ctx: Context<'_, '_, '_, 'info, BatchUndeleg
 
     msg!("Batch Undelegate executed for {} bets.", ctx.remaining_accounts.len());
     Ok(())
-}
\ No newline at end of file
+}
+
+#[commit]
+#[derive(Accounts)]
+pub struct BatchUndelegateBetPermissions<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: The MagicBlock Permission Program ID
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    pub permission_program: UncheckedAccount<'info>,
+}
+
+/// Permissionless, runs inside the rollup after `end_time`. Commits each bet
+/// permission back to L1 so it can later be closed. Remaining accounts are
+/// writable `(bet, permission)` pairs matching `request_ids` in order.
+pub fn batch_undelegate_bet_permissions<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchUndelegateBetPermissions<'info>>,
+    request_ids: Vec<String>,
+) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= ctx.accounts.pool.end_time,
+        CustomError::UndelegationTooEarly
+    );
+    require!(
+        ctx.remaining_accounts.len() == request_ids.len() * 2,
+        ErrorCode::AccountNotEnoughKeys
+    );
+
+    let permission_program = ctx.accounts.permission_program.to_account_info();
+    let magic_program = ctx.accounts.magic_program.to_account_info();
+    let magic_context = ctx.accounts.magic_context.to_account_info();
+
+    for (accounts, request_id) in ctx.remaining_accounts.chunks(2).zip(request_ids.iter()) {
+        let (bet_info, permission) = (&accounts[0], &accounts[1]);
+
+        let bet = {
+            let bet_data = bet_info.try_borrow_data()?;
+            Bet::try_deserialize(&mut &bet_data[..])?
+        };
+        require!(bet.pool_pubkey == pool_key, CustomError::PoolMismatch);
+        require!(
+            permission.key() == Permission::find_pda(&bet_info.key()).0,
+            CustomError::InvalidPermissionAccount
+        );
+
+        let seeds = &[
+            SEED_BET,
+            bet.pool_pubkey.as_ref(),
+            bet.user_pubkey.as_ref(),
+            request_id.as_bytes(),
+            &[bet.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        CommitAndUndelegatePermissionCpiBuilder::new(&permission_program)
+            .authority(bet_info, true)
+            .permissioned_account(bet_info, true)
+            .permission(permission)
+            .magic_program(&magic_program)
+            .magic_context(&magic_context)
+            .invoke_signed(signer_seeds)?;
+
+        emit!(BetPermissionUndelegated {
+            bet_address: bet_info.key(),
+        });
+    }
+
+    msg!("Undelegated {} bet permissions.", request_ids.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DelegateShieldedBalancePermission<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The owner's shielded balance (The Permissioned Account)
+    #[account(mut)]
+    pub shielded_balance: AccountInfo<'info>,
+
+    /// CHECK: The permission account associated with the shielded balance.
+    #[account(mut)]
+    pub permission: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Permission Program
+    pub permission_program: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Delegation Program
+    pub delegation_program: UncheckedAccount<'info>,
+
+    /// CHECK: Delegation buffer (Derived by client or SDK)
+    #[account(mut)]
+    pub delegation_buffer: UncheckedAccount<'info>,
+
+    /// CHECK: Delegation record (Derived by client or SDK)
+    #[account(mut)]
+    pub delegation_record: UncheckedAccount<'info>,
+
+    /// CHECK: Delegation metadata (Derived by client or SDK)
+    #[account(mut)]
+    pub delegation_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
+    pub validator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn delegate_shielded_balance_permission(ctx: Context<DelegateShieldedBalancePermission>) -> Result<()> {
+    let (owner, mint, bump) = {
+        let balance_data = ctx.accounts.shielded_balance.try_borrow_data()?;
+        let mut data_slice: &[u8] = &balance_data;
+        let balance = ShieldedBalance::try_deserialize(&mut data_slice)?;
+        (balance.owner, balance.mint, balance.bump)
+    };
+
+    require!(owner == ctx.accounts.owner.key(), CustomError::Unauthorized);
+
+    let seeds_for_signing = &[
+        SEED_SHIELDED_BALANCE,
+        mint.as_ref(),
+        owner.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds_for_signing[..]];
+
+    DelegatePermissionCpiBuilder::new(&ctx.accounts.permission_program)
+        .payer(&ctx.accounts.owner)
+        .authority(&ctx.accounts.owner, false)
+        .permissioned_account(&ctx.accounts.shielded_balance, true)
+        .permission(&ctx.accounts.permission)
+        .system_program(&ctx.accounts.system_program)
+        .owner_program(&ctx.accounts.permission_program)
+        .delegation_buffer(&ctx.accounts.delegation_buffer)
+        .delegation_record(&ctx.accounts.delegation_record)
+        .delegation_metadata(&ctx.accounts.delegation_metadata)
+        .delegation_program(&ctx.accounts.delegation_program)
+        .validator(Some(&ctx.accounts.validator))
+        .invoke_signed(signer_seeds)?;
+    msg!("Shielded balance permission delegated successfully.");
+    Ok(())
+}
+
+#[delegate]
+#[derive(Accounts)]
+pub struct DelegateShieldedBalance<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The owner's shielded balance.
+    #[account(mut, del)]
+    pub shielded_balance: AccountInfo<'info>,
+
+    /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
+    pub validator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+}
+
+/// A balance can fund bets in any pool of its mint, so it is not bound to a
+/// pool's pinned validator; it only works with pools delegated to the same one.
+pub fn delegate_shielded_balance(ctx: Context<DelegateShieldedBalance>) -> Result<()> {
+    let (owner, mint) = {
+        let balance_data = ctx.accounts.shielded_balance.try_borrow_data()?;
+        let mut data_slice: &[u8] = &balance_data;
+        let balance = ShieldedBalance::try_deserialize(&mut data_slice)?;
+        (balance.owner, balance.mint)
+    };
+
+    require!(owner == ctx.accounts.owner.key(), CustomError::Unauthorized);
+
+    let seeds_for_sdk = &[
+        SEED_SHIELDED_BALANCE,
+        mint.as_ref(),
+        owner.as_ref(),
+    ];
+
+    let config = DelegateConfig {
+        validator: Some(ctx.accounts.validator.key()),
+        ..DelegateConfig::default()
+    };
+
+    ctx.accounts.delegate_shielded_balance(
+        &ctx.accounts.owner,
+        seeds_for_sdk,
+        config,
+    )?;
+
+    msg!("Shielded balance delegated successfully.");
+    Ok(())
+}
+
+#[commit]
+#[derive(Accounts)]
+pub struct UndelegateShieldedBalance<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The owner's shielded balance, checked in the handler.
+    #[account(mut)]
+    pub shielded_balance: AccountInfo<'info>,
+}
+
+/// Commits the balance back to L1 so it can be withdrawn.
+pub fn undelegate_shielded_balance(ctx: Context<UndelegateShieldedBalance>) -> Result<()> {
+    let owner = {
+        let balance_data = ctx.accounts.shielded_balance.try_borrow_data()?;
+        let mut data_slice: &[u8] = &balance_data;
+        ShieldedBalance::try_deserialize(&mut data_slice)?.owner
+    };
+
+    require!(owner == ctx.accounts.owner.key(), CustomError::Unauthorized);
+
+    commit_and_undelegate_accounts(
+        &ctx.accounts.owner,
+        vec![&ctx.accounts.shielded_balance],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DelegatePoolAggregatesPermission<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::CrankOperator, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    /// CHECK: Manually validated against the aggregates' pool.
+    pub pool: AccountInfo<'info>,
+
+    /// CHECK: The pool's aggregates (The Permissioned Account)
+    #[account(mut)]
+    pub pool_aggregates: AccountInfo<'info>,
+
+    /// CHECK: The permission account associated with the aggregates.
+    #[account(mut)]
+    pub permission: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Permission Program
+    pub permission_program: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Delegation Program
+    pub delegation_program: UncheckedAccount<'info>,
+
+    /// CHECK: Delegation buffer (Derived by client or SDK)
+    #[account(mut)]
+    pub delegation_buffer: UncheckedAccount<'info>,
+
+    /// CHECK: Delegation record (Derived by client or SDK)
+    #[account(mut)]
+    pub delegation_record: UncheckedAccount<'info>,
+
+    /// CHECK: Delegation metadata (Derived by client or SDK)
+    #[account(mut)]
+    pub delegation_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
+    pub validator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn delegate_pool_aggregates_permission(ctx: Context<DelegatePoolAggregatesPermission>) -> Result<()> {
+    let (pool, bump) = {
+        let aggregates_data = ctx.accounts.pool_aggregates.try_borrow_data()?;
+        let mut data_slice: &[u8] = &aggregates_data;
+        let aggregates = PoolAggregates::try_deserialize(&mut data_slice)?;
+        (aggregates.pool, aggregates.bump)
+    };
+
+    require!(pool == ctx.accounts.pool.key(), CustomError::PoolMismatch);
+    check_pool_validator(&ctx.accounts.pool, &ctx.accounts.validator.key())?;
+
+    let seeds_for_signing = &[
+        SEED_POOL_AGGREGATES,
+        pool.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds_for_signing[..]];
+
+    DelegatePermissionCpiBuilder::new(&ctx.accounts.permission_program)
+        .payer(&ctx.accounts.admin)
+        .authority(&ctx.accounts.admin, false)
+        .permissioned_account(&ctx.accounts.pool_aggregates, true)
+        .permission(&ctx.accounts.permission)
+        .system_program(&ctx.accounts.system_program)
+        .owner_program(&ctx.accounts.permission_program)
+        .delegation_buffer(&ctx.accounts.delegation_buffer)
+        .delegation_record(&ctx.accounts.delegation_record)
+        .delegation_metadata(&ctx.accounts.delegation_metadata)
+        .delegation_program(&ctx.accounts.delegation_program)
+        .validator(Some(&ctx.accounts.validator))
+        .invoke_signed(signer_seeds)?;
+    msg!("Pool aggregates permission delegated successfully.");
+    Ok(())
+}
+
+#[delegate]
+#[derive(Accounts)]
+pub struct DelegatePoolAggregates<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::CrankOperator, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    /// CHECK: Manually validated against the aggregates' pool.
+    pub pool: AccountInfo<'info>,
+
+    /// CHECK: The pool's aggregates.
+    #[account(mut, del)]
+    pub pool_aggregates: AccountInfo<'info>,
+
+    /// CHECK: The MagicBlock Ephemeral Rollup Validator (TEE)
+    pub validator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [SEED_APPROVED_VALIDATOR, validator.key().as_ref()],
+        bump = approved_validator.bump
+    )]
+    pub approved_validator: Account<'info, ApprovedValidator>,
+}
+
+pub fn delegate_pool_aggregates(ctx: Context<DelegatePoolAggregates>) -> Result<()> {
+    let pool = {
+        let aggregates_data = ctx.accounts.pool_aggregates.try_borrow_data()?;
+        let mut data_slice: &[u8] = &aggregates_data;
+        PoolAggregates::try_deserialize(&mut data_slice)?.pool
+    };
+
+    require!(pool == ctx.accounts.pool.key(), CustomError::PoolMismatch);
+    check_pool_validator(&ctx.accounts.pool, &ctx.accounts.validator.key())?;
+
+    let seeds_for_sdk = &[
+        SEED_POOL_AGGREGATES,
+        pool.as_ref(),
+    ];
+
+    let config = DelegateConfig {
+        validator: Some(ctx.accounts.validator.key()),
+        ..DelegateConfig::default()
+    };
+
+    ctx.accounts.delegate_pool_aggregates(
+        &ctx.accounts.admin,
+        seeds_for_sdk,
+        config,
+    )?;
+
+    msg!("Pool aggregates delegated successfully.");
+    Ok(())
+}
+
+#[commit]
+#[derive(Accounts)]
+pub struct UndelegatePoolAggregates<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: The pool's aggregates, checked against the pool in the handler.
+    #[account(mut)]
+    pub pool_aggregates: AccountInfo<'info>,
+}
+
+/// Permissionless once betting has closed. Commits the aggregates back to L1
+/// for `sync_pool_aggregates`.
+pub fn undelegate_pool_aggregates(ctx: Context<UndelegatePoolAggregates>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.is_cancelled || clock.unix_timestamp >= pool.end_time,
+        CustomError::UndelegationTooEarly
+    );
+
+    let aggregates_pool = {
+        let aggregates_data = ctx.accounts.pool_aggregates.try_borrow_data()?;
+        let mut data_slice: &[u8] = &aggregates_data;
+        PoolAggregates::try_deserialize(&mut data_slice)?.pool
+    };
+    require!(aggregates_pool == pool.key(), CustomError::PoolMismatch);
+
+    commit_and_undelegate_accounts(
+        &ctx.accounts.payer,
+        vec![&ctx.accounts.pool_aggregates],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+
+    Ok(())
+}
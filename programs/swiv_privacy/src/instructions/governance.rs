@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use crate::state::{ConfigChange, Governance, GovernanceAction, Pool, Proposal, Protocol, Role};
+use crate::constants::{
+    MAX_GOVERNANCE_SIGNERS, PAUSE_SETTLEMENT, SEED_CONFIG_CHANGE, SEED_GOVERNANCE, SEED_POOL, SEED_PROPOSAL,
+    SEED_PROTOCOL,
+};
+use crate::errors::CustomError;
+use crate::events::{GovernanceUpdated, ProposalApproved, ProposalCreated, ProposalExecuted};
+use crate::instructions::admin::{
+    apply_accept_admin, apply_cancel_admin_transfer, apply_pause, record_config_change,
+};
+use crate::instructions::pool::{apply_cancellation, apply_resolution};
+
+fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_GOVERNANCE_SIGNERS,
+        CustomError::InvalidGovernanceConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        CustomError::InvalidGovernanceConfig
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            !signers[..i].contains(signer),
+            CustomError::InvalidGovernanceConfig
+        );
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Governance::LEN,
+        seeds = [SEED_GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the signer set. Admin authority is handed over separately: the
+/// admin queues a `ConfigParam::Admin` change naming the governance PDA and,
+/// once it executes, the signers pass an `AcceptAdmin` proposal.
+pub fn initialize_governance(
+    ctx: Context<InitializeGovernance>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_signers(&signers, threshold)?;
+
+    let governance = &mut ctx.accounts.governance;
+    governance.signers = signers.clone();
+    governance.threshold = threshold;
+    governance.proposal_count = 0;
+    governance.bump = ctx.bumps.governance;
+
+    emit!(GovernanceUpdated {
+        signers,
+        threshold,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(action: GovernanceAction)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_GOVERNANCE],
+        bump = governance.bump,
+        constraint = governance.is_signer(&proposer.key()) @ CustomError::NotGovernanceSigner
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [SEED_PROPOSAL, &governance.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal(ctx: Context<CreateProposal>, action: GovernanceAction) -> Result<()> {
+    if let GovernanceAction::UpdateSigners { signers, threshold } = &action {
+        validate_signers(signers, *threshold)?;
+    }
+
+    let governance = &mut ctx.accounts.governance;
+    let proposal = &mut ctx.accounts.proposal;
+    let proposer = ctx.accounts.proposer.key();
+
+    proposal.id = governance.proposal_count;
+    proposal.proposer = proposer;
+    proposal.action = action.clone();
+    proposal.approvals = vec![proposer];
+    proposal.executed = false;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+
+    governance.proposal_count = governance.proposal_count.checked_add(1).unwrap();
+
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        id: proposal.id,
+        proposer,
+        action,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_GOVERNANCE],
+        bump = governance.bump,
+        constraint = governance.is_signer(&signer.key()) @ CustomError::NotGovernanceSigner
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROPOSAL, &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ CustomError::ProposalAlreadyExecuted
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let governance = &ctx.accounts.governance;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
+
+    require!(
+        !proposal.is_expired(Clock::get()?.unix_timestamp),
+        CustomError::ProposalExpired
+    );
+    require!(!proposal.approvals.contains(&signer), CustomError::AlreadyApproved);
+
+    // Drop approvals from signers removed by `UpdateSigners`, so the list never
+    // outgrows the space reserved for a full signer set.
+    proposal.approvals.retain(|key| governance.is_signer(key));
+    proposal.approvals.push(signer);
+
+    emit!(ProposalApproved {
+        proposal: proposal.key(),
+        signer,
+        approvals: proposal.approvals.len() as u8,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_GOVERNANCE],
+        bump = governance.bump,
+        constraint = governance.is_signer(&executor.key()) @ CustomError::NotGovernanceSigner
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROPOSAL, &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ CustomError::ProposalAlreadyExecuted
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// Required for `CancelPool` and `ResolvePool`.
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Option<Box<Account<'info, Pool>>>,
+
+    /// Required for `QueueConfigChange`.
+    #[account(
+        init,
+        payer = executor,
+        space = ConfigChange::LEN,
+        seeds = [SEED_CONFIG_CHANGE, &protocol.config_change_count.to_le_bytes()],
+        bump
+    )]
+    pub config_change: Option<Box<Account<'info, ConfigChange>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let governance_key = ctx.accounts.governance.key();
+    let governance = &mut ctx.accounts.governance;
+    let protocol = &mut ctx.accounts.protocol;
+
+    require!(
+        !ctx.accounts.proposal.is_expired(Clock::get()?.unix_timestamp),
+        CustomError::ProposalExpired
+    );
+
+    // Approvals from signers removed since the proposal was created no longer count.
+    let approvals = ctx
+        .accounts
+        .proposal
+        .approvals
+        .iter()
+        .filter(|key| governance.is_signer(key))
+        .count();
+    require!(
+        approvals >= governance.threshold as usize,
+        CustomError::ThresholdNotMet
+    );
+
+    let action = ctx.accounts.proposal.action.clone();
+    match &action {
+        GovernanceAction::AcceptAdmin => {
+            apply_accept_admin(protocol, governance_key)?;
+        }
+        GovernanceAction::CancelAdminTransfer => {
+            require!(protocol.admin == governance_key, CustomError::Unauthorized);
+            apply_cancel_admin_transfer(protocol)?;
+        }
+        GovernanceAction::SetPause { paused } => {
+            require!(
+                protocol.has_role(Role::Pauser, &governance_key),
+                CustomError::Unauthorized
+            );
+            apply_pause(protocol, *paused)?;
+        }
+        GovernanceAction::CancelPool { pool, .. } | GovernanceAction::ResolvePool { pool, .. } => {
+            require!(
+                protocol.has_role(Role::Resolver, &governance_key),
+                CustomError::Unauthorized
+            );
+            let pool_account = ctx
+                .accounts
+                .pool
+                .as_mut()
+                .ok_or(CustomError::PoolMismatch)?;
+            require!(pool_account.key() == *pool, CustomError::PoolMismatch);
+
+            match &action {
+                GovernanceAction::ResolvePool { final_outcome, .. } => {
+                    // Same guards as `resolve_pool`.
+                    require!(
+                        !protocol.is_paused(PAUSE_SETTLEMENT),
+                        CustomError::Paused
+                    );
+                    require!(!pool_account.frozen, CustomError::PoolFrozen);
+                    apply_resolution(pool_account, *final_outcome)?;
+                }
+                GovernanceAction::CancelPool { creator_fault, .. } => {
+                    apply_cancellation(pool_account, *creator_fault)?;
+                }
+                _ => unreachable!(),
+            }
+        }
+        GovernanceAction::QueueConfigChange { change } => {
+            require!(protocol.admin == governance_key, CustomError::Unauthorized);
+            let config_change = ctx
+                .accounts
+                .config_change
+                .as_mut()
+                .ok_or(CustomError::SeedMismatch)?;
+            let bump = ctx.bumps.config_change.ok_or(CustomError::SeedMismatch)?;
+            record_config_change(protocol, config_change, governance_key, change.clone(), bump)?;
+        }
+        GovernanceAction::UpdateSigners { signers, threshold } => {
+            validate_signers(signers, *threshold)?;
+            governance.signers = signers.clone();
+            governance.threshold = *threshold;
+
+            emit!(GovernanceUpdated {
+                signers: signers.clone(),
+                threshold: *threshold,
+            });
+        }
+    }
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.executed = true;
+
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        action,
+    });
+
+    Ok(())
+}
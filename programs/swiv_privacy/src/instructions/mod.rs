@@ -2,8 +2,14 @@ pub mod admin;
 pub mod pool;
 pub mod delegation;
 pub mod permission;
+pub mod governance;
+pub mod shielded;
+pub mod pool_aggregates;
 
 pub use admin::*;
 pub use pool::*;
 pub use delegation::*;
-pub use permission::*;
\ No newline at end of file
+pub use permission::*;
+pub use governance::*;
+pub use shielded::*;
+pub use pool_aggregates::*;
\ No newline at end of file
@@ -1,9 +1,23 @@
-use crate::constants::SEED_BET;
+use crate::constants::{
+    MAX_BET_VIEWERS, SEED_BET, SEED_POOL, SEED_POOL_AGGREGATES, SEED_PROTOCOL,
+    SEED_SHIELDED_BALANCE,
+};
 use crate::errors::CustomError;
+use crate::events::BetPermissionClosed;
+use crate::state::{Bet, BetStatus, Pool, PoolAggregates, Protocol, Role, ShieldedBalance};
 use anchor_lang::prelude::*;
 
-use ephemeral_rollups_sdk::access_control::instructions::CreatePermissionCpiBuilder;
-use ephemeral_rollups_sdk::access_control::structs::{Member, MembersArgs, AUTHORITY_FLAG};
+use ephemeral_rollups_sdk::access_control::instructions::{
+    ClosePermissionCpiBuilder, CreatePermissionCpiBuilder, UpdatePermissionCpiBuilder,
+};
+use ephemeral_rollups_sdk::access_control::structs::{
+    Member, MembersArgs, Permission, AUTHORITY_FLAG, TX_BALANCES_FLAG, TX_LOGS_FLAG,
+    TX_MESSAGE_FLAG,
+};
+use ephemeral_rollups_sdk::consts::PERMISSION_PROGRAM_ID;
+
+/// Flags given to the compliance viewer: it can read, but has no authority.
+const COMPLIANCE_VIEWER_FLAGS: u8 = TX_LOGS_FLAG | TX_BALANCES_FLAG | TX_MESSAGE_FLAG;
 
 #[derive(Accounts)]
 #[instruction(request_id: String)]
@@ -14,7 +28,14 @@ pub struct CreateBetPermission<'info> {
     /// CHECK: The user who is given authority
     pub user: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
     /// CHECK: We manually verify seeds below to invoke with canonical bump
+    #[account(mut)]
     pub user_bet: UncheckedAccount<'info>,
 
     /// CHECK: Passed to permission program. Must be UncheckedAccount.
@@ -59,12 +80,29 @@ pub fn create_bet_permission(ctx: Context<CreateBetPermission>, request_id: Stri
     let seed_refs: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
     let signer_seeds = &[seed_refs.as_slice()];
 
-    let member = Member {
+    let mut members = vec![Member {
         pubkey: ctx.accounts.user.key(),
         flags: AUTHORITY_FLAG,
-    };
+    }];
+    let compliance_viewer = ctx.accounts.protocol.compliance_viewer;
+    if compliance_viewer != Pubkey::default() {
+        members.push(Member {
+            pubkey: compliance_viewer,
+            flags: COMPLIANCE_VIEWER_FLAGS,
+        });
+    }
+
+    // Recorded on the bet so viewer changes keep recognising this member.
+    {
+        let user_bet = &ctx.accounts.user_bet;
+        require!(*user_bet.owner == crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let mut bet_data = user_bet.try_borrow_mut_data()?;
+        let mut bet = Bet::try_deserialize(&mut &bet_data[..])?;
+        bet.compliance_viewer = compliance_viewer;
+        bet.try_serialize(&mut &mut bet_data[..])?;
+    }
     let args = MembersArgs {
-        members: Some(vec![member]),
+        members: Some(members),
     };
 
     CreatePermissionCpiBuilder::new(&ctx.accounts.permission_program)
@@ -77,3 +115,370 @@ pub fn create_bet_permission(ctx: Context<CreateBetPermission>, request_id: Stri
 
     Ok(())
 }
+
+#[derive(Accounts)]
+#[instruction(request_id: String)]
+pub struct UpdateBetViewers<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_BET, bet.pool_pubkey.as_ref(), user.key().as_ref(), request_id.as_bytes()],
+        bump = bet.bump,
+        constraint = bet.user_pubkey == user.key() @ CustomError::Unauthorized
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// CHECK: Checked against the bet's permission PDA in the handler.
+    #[account(mut)]
+    pub permission: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Permission Program ID
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    pub permission_program: UncheckedAccount<'info>,
+}
+
+fn read_bet_members(accounts: &UpdateBetViewers) -> Result<Vec<Member>> {
+    let (permission_pda, _) = Permission::find_pda(&accounts.bet.key());
+    require!(
+        accounts.permission.key() == permission_pda,
+        CustomError::InvalidPermissionAccount
+    );
+
+    let data = accounts.permission.try_borrow_data()?;
+    let permission = Permission::deserialize(&mut &data[..])?;
+    Ok(permission.members.unwrap_or_default())
+}
+
+fn write_bet_members(
+    accounts: &UpdateBetViewers,
+    request_id: &str,
+    members: Vec<Member>,
+) -> Result<()> {
+    let bet = &accounts.bet;
+    let seeds = &[
+        SEED_BET,
+        bet.pool_pubkey.as_ref(),
+        bet.user_pubkey.as_ref(),
+        request_id.as_bytes(),
+        &[bet.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let bet_info = bet.to_account_info();
+    let user_info = accounts.user.to_account_info();
+    let permission_info = accounts.permission.to_account_info();
+    let permission_program_info = accounts.permission_program.to_account_info();
+
+    UpdatePermissionCpiBuilder::new(&permission_program_info)
+        .authority(&user_info, true)
+        .permissioned_account(&bet_info, true)
+        .permission(&permission_info)
+        .args(MembersArgs {
+            members: Some(members),
+        })
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// Lets the bettor grant read access on their bet, e.g. to an auditor or a
+/// portfolio tracker. Works on L1 before the permission is delegated, or
+/// inside the rollup afterwards. Viewers can never be given authority.
+pub fn add_bet_viewer(
+    ctx: Context<UpdateBetViewers>,
+    request_id: String,
+    viewer: Pubkey,
+    flags: u8,
+) -> Result<()> {
+    require!(
+        flags != 0 && flags & AUTHORITY_FLAG == 0,
+        CustomError::InvalidViewer
+    );
+
+    let mut members = read_bet_members(ctx.accounts)?;
+    require!(
+        !members.iter().any(|member| member.pubkey == viewer),
+        CustomError::InvalidViewer
+    );
+
+    let compliance_viewer = ctx.accounts.bet.compliance_viewer;
+    let extra_viewers = members
+        .iter()
+        .filter(|member| member.flags & AUTHORITY_FLAG == 0 && member.pubkey != compliance_viewer)
+        .count();
+    require!(extra_viewers < MAX_BET_VIEWERS, CustomError::TooManyViewers);
+
+    members.push(Member {
+        pubkey: viewer,
+        flags,
+    });
+    write_bet_members(ctx.accounts, &request_id, members)
+}
+
+/// Removes a viewer the bettor added. The bettor's own membership and the
+/// compliance viewer recorded on the bet cannot be removed.
+pub fn remove_bet_viewer(
+    ctx: Context<UpdateBetViewers>,
+    request_id: String,
+    viewer: Pubkey,
+) -> Result<()> {
+    require!(
+        viewer != ctx.accounts.user.key() && viewer != ctx.accounts.bet.compliance_viewer,
+        CustomError::InvalidViewer
+    );
+
+    let mut members = read_bet_members(ctx.accounts)?;
+    let count = members.len();
+    members.retain(|member| member.pubkey != viewer);
+    require!(members.len() < count, CustomError::InvalidViewer);
+
+    write_bet_members(ctx.accounts, &request_id, members)
+}
+
+/// Closes a claimed bet's permission account. The bet PDA signs the close
+/// and takes the rent, which is then passed on to the bet owner.
+fn close_permission_to_owner<'info>(
+    permission_program: &AccountInfo<'info>,
+    bet_info: &AccountInfo<'info>,
+    bet: &Bet,
+    permission: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    request_id: &str,
+) -> Result<()> {
+    require!(bet.status == BetStatus::Claimed, CustomError::BetNotClaimed);
+    require!(owner.key() == bet.user_pubkey, CustomError::Unauthorized);
+    require!(
+        permission.key() == Permission::find_pda(&bet_info.key()).0,
+        CustomError::InvalidPermissionAccount
+    );
+    // Delegated permissions are owned by the delegation program until
+    // `batch_undelegate_bet_permissions` commits them back.
+    require!(
+        *permission.owner == PERMISSION_PROGRAM_ID,
+        CustomError::PermissionStillDelegated
+    );
+
+    let seeds = &[
+        SEED_BET,
+        bet.pool_pubkey.as_ref(),
+        bet.user_pubkey.as_ref(),
+        request_id.as_bytes(),
+        &[bet.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let lamports_before = bet_info.lamports();
+    ClosePermissionCpiBuilder::new(permission_program)
+        .payer(bet_info)
+        .authority(bet_info, true)
+        .permissioned_account(bet_info, true)
+        .permission(permission)
+        .invoke_signed(signer_seeds)?;
+
+    let reclaimed = bet_info.lamports().saturating_sub(lamports_before);
+    if reclaimed > 0 {
+        **bet_info.try_borrow_mut_lamports()? -= reclaimed;
+        **owner.try_borrow_mut_lamports()? += reclaimed;
+    }
+
+    emit!(BetPermissionClosed {
+        bet_address: bet_info.key(),
+        reclaimed,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: String)]
+pub struct CloseBetPermission<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BET, bet.pool_pubkey.as_ref(), user.key().as_ref(), request_id.as_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// CHECK: Checked against the bet's permission PDA.
+    #[account(mut)]
+    pub permission: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Permission Program ID
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    pub permission_program: UncheckedAccount<'info>,
+}
+
+/// Reclaims the rent of a claimed bet's permission once both are back on L1,
+/// the permission through `batch_undelegate_bet_permissions`.
+pub fn close_bet_permission(ctx: Context<CloseBetPermission>, request_id: String) -> Result<()> {
+    close_permission_to_owner(
+        &ctx.accounts.permission_program.to_account_info(),
+        &ctx.accounts.bet.to_account_info(),
+        &ctx.accounts.bet,
+        &ctx.accounts.permission.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &request_id,
+    )
+}
+
+#[derive(Accounts)]
+pub struct BatchCloseBetPermissions<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: The MagicBlock Permission Program ID
+    #[account(address = PERMISSION_PROGRAM_ID)]
+    pub permission_program: UncheckedAccount<'info>,
+}
+
+/// Permissionless. Remaining accounts are `(bet, permission, bet owner)`
+/// triples, all writable, matching `request_ids` in order. Rent always goes
+/// back to each bet's owner.
+pub fn batch_close_bet_permissions<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchCloseBetPermissions<'info>>,
+    request_ids: Vec<String>,
+) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let permission_program = ctx.accounts.permission_program.to_account_info();
+
+    require!(
+        ctx.remaining_accounts.len() == request_ids.len() * 3,
+        ErrorCode::AccountNotEnoughKeys
+    );
+
+    for (accounts, request_id) in ctx.remaining_accounts.chunks(3).zip(request_ids.iter()) {
+        let (bet_info, permission, owner) = (&accounts[0], &accounts[1], &accounts[2]);
+        require!(*bet_info.owner == crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+        let bet = {
+            let bet_data = bet_info.try_borrow_data()?;
+            Bet::try_deserialize(&mut &bet_data[..])?
+        };
+        require!(bet.pool_pubkey == pool_key, CustomError::PoolMismatch);
+
+        close_permission_to_owner(
+            &permission_program,
+            bet_info,
+            &bet,
+            permission,
+            owner,
+            request_id,
+        )?;
+    }
+
+    msg!("Closed {} bet permissions.", request_ids.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateShieldedBalancePermission<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_SHIELDED_BALANCE, shielded_balance.mint.as_ref(), owner.key().as_ref()],
+        bump = shielded_balance.bump
+    )]
+    pub shielded_balance: Account<'info, ShieldedBalance>,
+
+    /// CHECK: Validated by Permission Program
+    #[account(mut)]
+    pub permission: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Permission Program ID
+    pub permission_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_shielded_balance_permission(ctx: Context<CreateShieldedBalancePermission>) -> Result<()> {
+    let shielded_balance = &ctx.accounts.shielded_balance;
+    let owner_key = ctx.accounts.owner.key();
+    let seeds = &[
+        SEED_SHIELDED_BALANCE,
+        shielded_balance.mint.as_ref(),
+        owner_key.as_ref(),
+        &[shielded_balance.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let member = Member {
+        pubkey: owner_key,
+        flags: AUTHORITY_FLAG,
+    };
+    let args = MembersArgs {
+        members: Some(vec![member]),
+    };
+
+    CreatePermissionCpiBuilder::new(&ctx.accounts.permission_program)
+        .payer(&ctx.accounts.owner)
+        .system_program(&ctx.accounts.system_program)
+        .permission(&ctx.accounts.permission)
+        .permissioned_account(&shielded_balance.to_account_info())
+        .args(args)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreatePoolAggregatesPermission<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::CrankOperator, &authority.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        seeds = [SEED_POOL_AGGREGATES, pool_aggregates.pool.as_ref()],
+        bump = pool_aggregates.bump
+    )]
+    pub pool_aggregates: Account<'info, PoolAggregates>,
+
+    /// CHECK: Validated by Permission Program
+    #[account(mut)]
+    pub permission: UncheckedAccount<'info>,
+
+    /// CHECK: The MagicBlock Permission Program ID
+    pub permission_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// The aggregates are only read and written by the program inside the
+/// rollup, so the permission has no members.
+pub fn create_pool_aggregates_permission(ctx: Context<CreatePoolAggregatesPermission>) -> Result<()> {
+    let pool_aggregates = &ctx.accounts.pool_aggregates;
+    let seeds = &[
+        SEED_POOL_AGGREGATES,
+        pool_aggregates.pool.as_ref(),
+        &[pool_aggregates.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let args = MembersArgs {
+        members: Some(vec![]),
+    };
+
+    CreatePermissionCpiBuilder::new(&ctx.accounts.permission_program)
+        .payer(&ctx.accounts.authority)
+        .system_program(&ctx.accounts.system_program)
+        .permission(&ctx.accounts.permission)
+        .permissioned_account(&pool_aggregates.to_account_info())
+        .args(args)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
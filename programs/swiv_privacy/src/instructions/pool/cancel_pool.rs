@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Protocol, Role};
+use crate::constants::{SEED_PROTOCOL, SEED_POOL};
+use crate::errors::CustomError;
+use crate::events::PoolCancelled;
+
+#[derive(Accounts)]
+pub struct CancelPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::Resolver, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Voids an unresolved pool. Bettors can then take their stake back through
+/// `emergency_refund` without waiting for the refund timeout. A pool voided
+/// for `creator_fault` forfeits its creator bond.
+pub fn cancel_pool(ctx: Context<CancelPool>, creator_fault: bool) -> Result<()> {
+    apply_cancellation(&mut ctx.accounts.pool, creator_fault)
+}
+
+pub(crate) fn apply_cancellation(pool: &mut Account<Pool>, creator_fault: bool) -> Result<()> {
+    require!(!pool.is_resolved, CustomError::AlreadyClaimed);
+    require!(!pool.is_cancelled, CustomError::PoolCancelled);
+
+    let clock = Clock::get()?;
+    pool.is_cancelled = true;
+    pool.cancelled_for_creator_fault = creator_fault;
+
+    emit!(PoolCancelled {
+        pool_address: pool.key(),
+        cancelled_at: clock.unix_timestamp,
+        creator_fault,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Protocol, ReferrerAccount};
+use crate::constants::{PAUSE_CLAIMS, SEED_FEE_VAULT, SEED_PROTOCOL, SEED_REFERRER};
+use crate::errors::CustomError;
+use crate::events::ReferralRewardsClaimed;
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    pub referrer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_CLAIMS) @ CustomError::Paused
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REFERRER, referrer.key().as_ref(), referrer_account.mint.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT, referrer_account.mint.as_ref()],
+        bump,
+        token::authority = protocol,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = referrer_account.mint,
+        token::authority = referrer,
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays out everything credited to the referrer for one mint.
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let referrer_account = &mut ctx.accounts.referrer_account;
+    let amount = referrer_account.claimable();
+    require!(amount > 0, CustomError::NoReferralRewards);
+
+    let bump = ctx.bumps.protocol;
+    let seeds = &[SEED_PROTOCOL, &[bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.referrer_token_account.to_account_info(),
+                authority: ctx.accounts.protocol.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    referrer_account.total_claimed = referrer_account.total_claimed.checked_add(amount).unwrap();
+
+    emit!(ReferralRewardsClaimed {
+        referrer: referrer_account.referrer,
+        mint: referrer_account.mint,
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,19 +1,32 @@
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT};
+use crate::constants::{SEED_FEE_LEDGER, SEED_FEE_VAULT, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_REFERRER, PAUSE_CLAIMS};
 use crate::errors::CustomError;
-use crate::state::{BetStatus, Pool, Bet};
-use crate::events::RewardClaimed;
+use crate::state::{BetStatus, FeeLedger, FeeMode, Pool, Bet, Protocol, ReferrerAccount};
+use crate::events::{ReferralRewardCredited, RewardClaimed};
+use crate::utils::math::calculate_fee;
+use crate::utils::signature::{authorize_bet_payout, payout_destination};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 pub struct ClaimReward<'info> {
+    /// Either the bet owner, or anyone relaying a claim the owner signed for
+    /// `user_token_account`.
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_CLAIMS) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
     #[account(
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -23,26 +36,105 @@ pub struct ClaimReward<'info> {
         bump,
         token::authority = pool,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub pool_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         mut,
-        constraint = bet.user_pubkey == user.key() @ CustomError::Unauthorized,
+        constraint = bet.pool_pubkey == pool.key() @ CustomError::PoolMismatch,
+        constraint = bet.status != BetStatus::Claimed @ CustomError::AlreadyClaimed
     )]
     pub bet: Box<Account<'info, Bet>>,
 
+    /// For native SOL pools this is a temporary wrapped SOL account owned by
+    /// `user`, closed into `recipient`.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Native SOL pools only: the wallet that receives the unwrapped
+    /// lamports, and the destination the owner signs for. Defaults to `user`.
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    /// Required when the bet was referred and a referral fee is owed on it.
+    #[account(
+        mut,
+        seeds = [SEED_REFERRER, referrer_account.referrer.as_ref(), pool.stake_token_mint.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Option<Box<Account<'info, ReferrerAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_LEDGER, pool.stake_token_mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT, pool.stake_token_mint.as_ref()],
+        bump,
+        token::authority = protocol,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Required for profit-fee pools when the pool creator earns a fee share.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Instructions sysvar, required when the bet owner authorizes the claim by signature.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
 
     pub token_program: Program<'info, Token>,
 }
 
+fn pay_from_vault<'info>(
+    accounts: &ClaimReward<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.pool_vault.to_account_info(),
+                to,
+                authority: accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
 pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-    let bet = &mut ctx.accounts.bet;
+    let pool = &ctx.accounts.pool;
+    let bet = &ctx.accounts.bet;
+    let protocol = &ctx.accounts.protocol;
     let mut payout_amount: u64 = 0;
 
     require!(pool.weight_finalized, CustomError::SettlementTooEarly);
+    authorize_bet_payout(
+        &bet.user_pubkey,
+        &ctx.accounts.user.key(),
+        &bet.key(),
+        &payout_destination(
+            pool,
+            &ctx.accounts.user,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.recipient,
+        ),
+        ctx.accounts.instructions_sysvar.as_ref().map(|ix| ix.as_ref()),
+    )?;
 
     if bet.calculated_weight > 0 && pool.total_weight > 0 {
         let total_distributable_pot = pool.total_volume as u128;
@@ -55,39 +147,135 @@ pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
             .unwrap() as u64;
     }
 
-    if payout_amount > 0 {
+    require!(
+        payout_amount <= pool.total_volume,
+        CustomError::InsufficientLiquidity
+    );
+
+    // Profit-fee pools were finalized without a fee, so it is taken here and
+    // only on the part of the payout above the bet's own stake.
+    let mut fee_amount: u64 = 0;
+    let mut creator_fee: u64 = 0;
+    let mut referral_amount: u64 = 0;
+
+    if pool.fee_mode == FeeMode::OnProfit && payout_amount > bet.stake {
+        fee_amount = calculate_fee(payout_amount - bet.stake, pool.fee_bps)?;
+        creator_fee = calculate_fee(fee_amount, protocol.creator_fee_share_bps)?;
+        if bet.referrer.is_some() {
+            referral_amount = calculate_fee(fee_amount, protocol.referrer_fee_share_bps)?;
+        }
+    }
+    let treasury_fee = fee_amount - creator_fee - referral_amount;
+
+    // Volume-fee pools held the referral share back at finalization, unless
+    // `credit_referral_fees` has already credited it.
+    if bet.referrer.is_some() && !bet.referral_credited {
+        let held_back_share = pool.held_back_referral_share(bet.stake);
+        referral_amount = referral_amount.checked_add(held_back_share).unwrap();
+    }
+
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    let user_amount = payout_amount - fee_amount;
+    if user_amount > 0 {
+        pay_from_vault(
+            ctx.accounts,
+            ctx.accounts.user_token_account.to_account_info(),
+            user_amount,
+            signer,
+        )?;
+    }
+
+    if treasury_fee > 0 {
+        pay_from_vault(
+            ctx.accounts,
+            ctx.accounts.fee_vault.to_account_info(),
+            treasury_fee,
+            signer,
+        )?;
+    }
+
+    if creator_fee > 0 {
+        let creator_token_account = ctx
+            .accounts
+            .creator_token_account
+            .as_ref()
+            .ok_or(CustomError::MissingTokenAccount)?;
+
+        pay_from_vault(
+            ctx.accounts,
+            creator_token_account.to_account_info(),
+            creator_fee,
+            signer,
+        )?;
+    }
+
+    // Referral rebates move into the fee vault and are credited to the
+    // referrer, who collects them with `claim_referral_rewards`.
+    if let (Some(referrer), true) = (bet.referrer, referral_amount > 0) {
+        pay_from_vault(
+            ctx.accounts,
+            ctx.accounts.fee_vault.to_account_info(),
+            referral_amount,
+            signer,
+        )?;
+
+        let referrer_account = ctx
+            .accounts
+            .referrer_account
+            .as_mut()
+            .ok_or(CustomError::MissingReferrerAccount)?;
         require!(
-            payout_amount <= pool.total_volume,
-            CustomError::InsufficientLiquidity
+            referrer_account.referrer == referrer,
+            CustomError::MissingReferrerAccount
         );
+        referrer_account.total_earned = referrer_account.total_earned.checked_add(referral_amount).unwrap();
 
-        let created_by_bytes = pool.created_by.as_ref();
-        let pool_id_bytes = pool.pool_id.to_le_bytes();
-        let bump = pool.bump;
-        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-        let signer = &[&seeds[..]];
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.pool_vault.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
-            payout_amount,
-        )?;
-        
+        emit!(ReferralRewardCredited {
+            bet_address: bet.key(),
+            referrer,
+            amount: referral_amount,
+        });
+    }
+
+    if pool.is_native() {
+        require!(
+            ctx.accounts.user_token_account.owner == ctx.accounts.user.key(),
+            CustomError::Unauthorized
+        );
+
+        let recipient = ctx
+            .accounts
+            .recipient
+            .as_ref()
+            .map_or(ctx.accounts.user.to_account_info(), |recipient| recipient.to_account_info());
+
+        token::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                destination: recipient,
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
     }
 
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+    fee_ledger.total_accrued = fee_ledger.total_accrued.checked_add(treasury_fee).unwrap();
+
+    let bet = &mut ctx.accounts.bet;
     bet.status = BetStatus::Claimed;
+    bet.referral_credited = true;
 
     emit!(RewardClaimed {
         bet_address: bet.key(),
         user: ctx.accounts.user.key(),
-        amount: payout_amount,
+        amount: user_amount,
+        fee: fee_amount,
     });
 
     Ok(())
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
-use crate::state::{Pool, Protocol};
-use crate::constants::{SEED_PROTOCOL, SEED_POOL, SEED_POOL_VAULT}; 
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{FeeMode, Pool, PrivacyPolicy, Protocol, Role, WhitelistedCreator};
+use crate::constants::{
+    MAX_POOL_TITLE_LEN, MAX_PROTOCOL_FEE_BPS, SEED_BOND_VAULT, SEED_CREATOR_WHITELIST, SEED_PROTOCOL,
+    SEED_POOL, SEED_POOL_VAULT, PAUSE_POOL_CREATION,
+};
 use crate::errors::CustomError;
 use crate::events::PoolCreated;
 
@@ -12,25 +15,29 @@ use crate::events::PoolCreated;
     start_time: i64, 
     end_time: i64, 
     max_accuracy_buffer: u64,
-    conviction_bonus_bps: u64
+    conviction_bonus_bps: u64,
+    fee_bps_override: Option<u64>,
+    fee_mode: FeeMode,
+    seed_amount: u64,
+    privacy: PrivacyPolicy
 )]
 pub struct CreatePool<'info> {
     #[account(
         mut,
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == created_by.key() @ CustomError::Unauthorized
+        constraint = !protocol.is_paused(PAUSE_POOL_CREATION) @ CustomError::Paused
     )]
-    pub protocol: Account<'info, Protocol>,
+    pub protocol: Box<Account<'info, Protocol>>,
 
     #[account(
         init,
         payer = created_by,
-        space = 8 + 300, 
+        space = Pool::LEN,
         seeds = [SEED_POOL, created_by.key().as_ref(), &pool_id.to_le_bytes()],
         bump
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: Box<Account<'info, Pool>>,
 
     #[account(
         init,
@@ -40,21 +47,40 @@ pub struct CreatePool<'info> {
         token::mint = token_mint,
         token::authority = pool,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub pool_vault: Box<Account<'info, TokenAccount>>,
     
-    pub token_mint: Account<'info, token::Mint>,
+    pub token_mint: Box<Account<'info, token::Mint>>,
 
     #[account(mut)]
     pub created_by: Signer<'info>,
 
     #[account(mut)]
-    pub created_by_token_account: Account<'info, TokenAccount>,
+    pub created_by_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Required for bonded creators while the creator whitelist is enabled.
+    #[account(
+        seeds = [SEED_CREATOR_WHITELIST, created_by.key().as_ref()],
+        bump = creator_whitelist.bump
+    )]
+    pub creator_whitelist: Option<Account<'info, WhitelistedCreator>>,
+
+    /// Required when the creator has to post a bond.
+    #[account(
+        init,
+        payer = created_by,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool,
+    )]
+    pub bond_vault: Option<Box<Account<'info, TokenAccount>>>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_pool(
     ctx: Context<CreatePool>,
     pool_id: u64,
@@ -63,13 +89,73 @@ pub fn create_pool(
     end_time: i64,
     max_accuracy_buffer: u64,
     conviction_bonus_bps: u64,
+    fee_bps_override: Option<u64>,
+    fee_mode: FeeMode,
+    seed_amount: u64,
+    privacy: PrivacyPolicy,
 ) -> Result<()> {
     require!(end_time > start_time, CustomError::DurationTooShort);
+    require!(title.len() <= MAX_POOL_TITLE_LEN, CustomError::TitleTooLong);
+    if let Some(fee_bps) = fee_bps_override {
+        require!(fee_bps <= MAX_PROTOCOL_FEE_BPS, CustomError::FeeTooHigh);
+    }
+
+    let creator = ctx.accounts.created_by.key();
+    let protocol = &ctx.accounts.protocol;
+
+    // Pool creator role holders are trusted; anyone else has to post a bond.
+    let creator_bond = if protocol.has_role(Role::PoolCreator, &creator) {
+        0
+    } else {
+        require!(protocol.open_pool_creation, CustomError::PoolCreationClosed);
+        require!(
+            !protocol.creator_whitelist_enabled || ctx.accounts.creator_whitelist.is_some(),
+            CustomError::CreatorNotWhitelisted
+        );
+        require!(fee_bps_override.is_none(), CustomError::Unauthorized);
+        protocol.creator_bond_amount
+    };
+
+    if creator_bond > 0 {
+        let bond_vault = ctx
+            .accounts
+            .bond_vault
+            .as_ref()
+            .ok_or(CustomError::MissingBondVault)?;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.created_by_token_account.to_account_info(),
+                    to: bond_vault.to_account_info(),
+                    authority: ctx.accounts.created_by.to_account_info(),
+                },
+            ),
+            creator_bond,
+        )?;
+    }
+
+    // The seed subsidy sits in the pool vault next to user stakes but is kept
+    // out of `total_volume`, so it is never charged a volume fee.
+    if seed_amount > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.created_by_token_account.to_account_info(),
+                    to: ctx.accounts.pool_vault.to_account_info(),
+                    authority: ctx.accounts.created_by.to_account_info(),
+                },
+            ),
+            seed_amount,
+        )?;
+    }
 
     let pool = &mut ctx.accounts.pool;
     let protocol = &mut ctx.accounts.protocol;
     
-    pool.created_by = ctx.accounts.created_by.key();
+    pool.created_by = creator;
     pool.title = title.clone();
     pool.pool_id = pool_id;
     pool.stake_token_mint = ctx.accounts.token_mint.key();
@@ -81,18 +167,38 @@ pub fn create_pool(
     pool.conviction_bonus_bps = conviction_bonus_bps; 
     
     pool.is_resolved = false;
+    pool.frozen = false;
+    pool.is_cancelled = false;
     pool.resolution_result = 0;
     
     pool.total_weight = 0;
     pool.weight_finalized = false;
     pool.bump = ctx.bumps.pool;
     
+    // Snapshot the fee so later config changes cannot reach pools already open.
+    pool.fee_bps = fee_bps_override.unwrap_or(protocol.protocol_fee_bps);
+    pool.fee_mode = fee_mode;
+    pool.version = Pool::CURRENT_VERSION;
+    pool.creator_bond = creator_bond;
+    pool.bond_settled = false;
+    pool.cancelled_for_creator_fault = false;
+    pool.seed_subsidy = seed_amount;
+    pool.subsidy_reclaimed = false;
+    pool.privacy = privacy;
+    pool.private_aggregates = false;
+    pool.aggregates_synced = false;
+    pool.pinned_validator = Pubkey::default();
+    
     protocol.total_pools = protocol.total_pools.checked_add(1).unwrap();
     
     emit!(PoolCreated {
         pool_name: title,
         start_time,
         end_time,
+        fee_bps: pool.fee_bps,
+        creator,
+        creator_bond,
+        seed_subsidy: seed_amount,
     });
 
     Ok(())
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Bet, BetStatus, Pool, Protocol, ReferrerAccount};
+use crate::constants::{PAUSE_CLAIMS, SEED_FEE_VAULT, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::ReferralRewardCredited;
+
+#[derive(Accounts)]
+pub struct CreditReferralFees<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_CLAIMS) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = pool.weight_finalized @ CustomError::SettlementTooEarly
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT, pool.stake_token_mint.as_ref()],
+        bump,
+        token::authority = protocol,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless crank that credits the referral share held back at
+/// finalization for bets that will never be claimed, such as losing ones.
+/// Remaining accounts are writable `(bet, referrer_account)` pairs; bets that
+/// are already claimed or credited are skipped.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn credit_referral_fees<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreditReferralFees<'info>>,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let pool_key = pool.key();
+
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        ErrorCode::AccountNotEnoughKeys
+    );
+
+    let mut total_credited: u64 = 0;
+
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let (bet_info, referrer_info) = (&accounts[0], &accounts[1]);
+
+        require!(*bet_info.owner == crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        require!(*referrer_info.owner == crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+        let mut bet_data = bet_info.try_borrow_mut_data()?;
+        let mut bet = Bet::try_deserialize(&mut &bet_data[..])?;
+        require!(bet.pool_pubkey == pool_key, CustomError::PoolMismatch);
+
+        let Some(referrer) = bet.referrer else { continue };
+        if bet.referral_credited || bet.status == BetStatus::Claimed {
+            continue;
+        }
+
+        let mut referrer_data = referrer_info.try_borrow_mut_data()?;
+        let mut referrer_account = ReferrerAccount::try_deserialize(&mut &referrer_data[..])?;
+        require!(
+            referrer_account.referrer == referrer
+                && referrer_account.mint == pool.stake_token_mint,
+            CustomError::MissingReferrerAccount
+        );
+
+        let amount = pool.held_back_referral_share(bet.stake);
+        referrer_account.total_earned = referrer_account.total_earned.checked_add(amount).unwrap();
+        bet.referral_credited = true;
+        total_credited = total_credited.checked_add(amount).unwrap();
+
+        referrer_account.try_serialize(&mut &mut referrer_data[..])?;
+        bet.try_serialize(&mut &mut bet_data[..])?;
+
+        emit!(ReferralRewardCredited {
+            bet_address: bet_info.key(),
+            referrer,
+            amount,
+        });
+    }
+
+    if total_credited > 0 {
+        let created_by_bytes = pool.created_by.as_ref();
+        let pool_id_bytes = pool.pool_id.to_le_bytes();
+        let bump = pool.bump;
+        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            total_credited,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,20 +1,31 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Bet, Pool, BetStatus};
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::{Bet, Pool, BetStatus, Protocol};
+use crate::constants::{
+    SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, PAUSE_CLAIMS, REFUND_TIMEOUT_SECONDS,
+};
 use crate::errors::CustomError;
 use crate::events::BetRefunded;
-
-const REFUND_TIMEOUT_SECONDS: i64 = 60; 
+use crate::utils::signature::{authorize_bet_payout, payout_destination};
+use anchor_lang::solana_program::sysvar;
 
 #[derive(Accounts)]
 pub struct EmergencyRefund<'info> {
+    /// Either the bet owner, or anyone relaying a refund the owner signed for
+    /// `user_token_account`.
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_CLAIMS) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
     #[account(
         mut,
-        constraint = bet.user_pubkey == user.key() @ CustomError::Unauthorized,
+        constraint = bet.pool_pubkey == pool.key() @ CustomError::PoolMismatch,
         constraint = bet.status != BetStatus::Claimed @ CustomError::AlreadyClaimed
     )]
     pub bet: Box<Account<'info, Bet>>,
@@ -22,7 +33,8 @@ pub struct EmergencyRefund<'info> {
     #[account(
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -34,12 +46,23 @@ pub struct EmergencyRefund<'info> {
     )]
     pub pool_vault: Box<Account<'info, TokenAccount>>,
 
+    /// For native SOL pools this is a temporary wrapped SOL account owned by
+    /// `user`, closed into `recipient`.
     #[account(
         mut,
         token::mint = pool.stake_token_mint
     )]
     pub user_token_account: Box<Account<'info, TokenAccount>>,
 
+    /// CHECK: Native SOL pools only: the wallet that receives the unwrapped
+    /// lamports, and the destination the owner signs for. Defaults to `user`.
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, required when the bet owner authorizes the refund by signature.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
 }
 
@@ -48,10 +71,30 @@ pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let clock = Clock::get()?;
 
+    authorize_bet_payout(
+        &bet.user_pubkey,
+        &ctx.accounts.user.key(),
+        &bet.key(),
+        &payout_destination(
+            pool,
+            &ctx.accounts.user,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.recipient,
+        ),
+        ctx.accounts.instructions_sysvar.as_ref().map(|ix| ix.as_ref()),
+    )?;
+
+    // Once an outcome is picked, stakes are settled through `claim_reward`.
+    require!(
+        !pool.is_resolved && !pool.weight_finalized,
+        CustomError::PoolAlreadyResolved
+    );
     require!(
-        clock.unix_timestamp > bet.end_timestamp + REFUND_TIMEOUT_SECONDS,
+        pool.is_cancelled || clock.unix_timestamp > bet.end_timestamp + REFUND_TIMEOUT_SECONDS,
         CustomError::TimeoutNotMet
     );
+    require!(pool.aggregates_settled(), CustomError::AggregatesNotSynced);
+    require!(pool.shielded_stakes_settled(), CustomError::ShieldedStakeUnsettled);
 
 
     let refund_amount = bet.stake;
@@ -79,12 +122,34 @@ pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
         pool.total_volume = pool.total_volume.checked_sub(refund_amount).unwrap();
     }
 
+    if pool.is_native() {
+        require!(
+            ctx.accounts.user_token_account.owner == ctx.accounts.user.key(),
+            CustomError::Unauthorized
+        );
+
+        let recipient = ctx
+            .accounts
+            .recipient
+            .as_ref()
+            .map_or(ctx.accounts.user.to_account_info(), |recipient| recipient.to_account_info());
+
+        token::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.user_token_account.to_account_info(),
+                destination: recipient,
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+    }
+
     bet.status = BetStatus::Claimed;
     
     emit!(BetRefunded {
         bet_address: bet.key(),
         user: ctx.accounts.user.key(),
-        amount: refund_amount,
+        amount: pool.reveals_stakes().then_some(refund_amount),
         is_emergency: true,
     });
 
@@ -1,9 +1,10 @@
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL};
+use crate::constants::{SEED_FEE_LEDGER, SEED_FEE_VAULT, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, PAUSE_SETTLEMENT};
 use crate::errors::CustomError;
 use crate::events::WeightsFinalized;
-use crate::state::{Pool, Protocol};
+use crate::state::{FeeLedger, FeeMode, Pool, Protocol, Role};
+use crate::utils::math::calculate_fee;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 pub struct FinalizeWeights<'info> {
@@ -13,6 +14,8 @@ pub struct FinalizeWeights<'info> {
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
+        constraint = protocol.has_role(Role::CrankOperator, &admin.key()) @ CustomError::Unauthorized,
+        constraint = !protocol.is_paused(PAUSE_SETTLEMENT) @ CustomError::Paused
     )]
     pub protocol: Account<'info, Protocol>,
 
@@ -20,6 +23,7 @@ pub struct FinalizeWeights<'info> {
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
         bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
     )]
     pub pool: Account<'info, Pool>,
 
@@ -31,10 +35,38 @@ pub struct FinalizeWeights<'info> {
     )]
     pub pool_vault: Account<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    #[account(address = pool.stake_token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = FeeLedger::LEN,
+        seeds = [SEED_FEE_LEDGER, token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [SEED_FEE_VAULT, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn finalize_weights(ctx: Context<FinalizeWeights>) -> Result<()> {
@@ -43,49 +75,91 @@ pub fn finalize_weights(ctx: Context<FinalizeWeights>) -> Result<()> {
 
     require!(pool.is_resolved, CustomError::SettlementTooEarly);
     require!(!pool.weight_finalized, CustomError::WeightsAlreadyFinalized);
+    require!(pool.aggregates_settled(), CustomError::AggregatesNotSynced);
+    require!(pool.shielded_stakes_settled(), CustomError::ShieldedStakeUnsettled);
 
+    // A subsidy reclaimed after the refund timeout has already left the vault.
     let total_assets = ctx.accounts.pool_vault.amount;
-    let mut distributable_amount = total_assets;
-    let mut fee_amount: u64 = 0;
-
-    if config.protocol_fee_bps > 0 {
-        fee_amount = (total_assets as u128)
-            .checked_mul(config.protocol_fee_bps as u128)
-            .unwrap()
-            .checked_div(10000)
-            .unwrap() as u64;
-
-        if fee_amount > 0 {
-            let created_by_bytes = pool.created_by.as_ref();
-            let pool_id_bytes = pool.pool_id.to_le_bytes();
-            let bump = pool.bump;
-            let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-            let signer = &[&seeds[..]];
-
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.pool_vault.to_account_info(),
-                        to: ctx.accounts.treasury_token_account.to_account_info(),
-                        authority: pool.to_account_info(),
-                    },
-                    signer,
-                ),
-                fee_amount,
-            )?;
-
-            distributable_amount = total_assets.checked_sub(fee_amount).unwrap();
-        }
+    let remaining_subsidy = if pool.subsidy_reclaimed { 0 } else { pool.seed_subsidy };
+    let staked_assets = total_assets.saturating_sub(remaining_subsidy);
+    let fee_amount = match pool.fee_mode {
+        FeeMode::OnVolume => calculate_fee(staked_assets, pool.fee_bps)?,
+        // Charged per winner in `claim_reward` instead.
+        FeeMode::OnProfit => 0,
+    };
+
+    // The referral share is only owed on referred stake, and is held back in the
+    // pool vault so each referred bet can pay its referrer out at claim time.
+    let creator_fee = calculate_fee(fee_amount, config.creator_fee_share_bps)?;
+    let referral_fee = if fee_amount > 0 {
+        calculate_fee(
+            calculate_fee(pool.referred_volume, pool.fee_bps)?,
+            config.referrer_fee_share_bps,
+        )?
+        .min(fee_amount - creator_fee)
+    } else {
+        0
+    };
+    let treasury_fee = fee_amount - creator_fee - referral_fee;
+
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    if treasury_fee > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            treasury_fee,
+        )?;
+    }
+
+    if creator_fee > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            creator_fee,
+        )?;
     }
 
-    pool.total_volume = distributable_amount;
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+    fee_ledger.mint = ctx.accounts.token_mint.key();
+    fee_ledger.bump = ctx.bumps.fee_ledger;
+    fee_ledger.total_accrued = fee_ledger.total_accrued.checked_add(treasury_fee).unwrap();
+
+    // With no winning weight the seed subsidy stays reclaimable by the creator
+    // instead of joining the pot.
+    let distributable = if pool.total_weight > 0 {
+        total_assets
+    } else {
+        staked_assets
+    };
+    pool.total_volume = distributable.checked_sub(fee_amount).unwrap();
+    pool.referral_fees = referral_fee;
     pool.weight_finalized = true;
 
     emit!(WeightsFinalized {
         pool_name: pool.title.clone(),
         total_weight: pool.total_weight,
         fee_deducted: fee_amount,
+        creator_fee,
+        referral_fee,
     });
 
     Ok(())
@@ -1,26 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Protocol, Pool, Bet, BetStatus};
-use crate::constants::{SEED_BET, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL}; 
+use anchor_lang::system_program;
+use anchor_spl::token::{self, SyncNative, Token, TokenAccount, Transfer};
+use crate::state::{Protocol, Pool, Bet, BetStatus, ReferrerAccount};
+use crate::constants::{SEED_BET, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_REFERRER, PAUSE_BET_INTAKE}; 
 use crate::errors::CustomError;
 
 #[derive(Accounts)]
-#[instruction(amount: u64, request_id: String)]
+#[instruction(amount: u64, request_id: String, referrer: Option<Pubkey>)]
 pub struct InitBet<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// A one-time key that owns the bet instead of `user`. It must co-sign, so
+    /// nobody can open a bet at another key's address.
+    pub bet_owner: Option<Signer<'info>>,
+
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = !protocol.paused @ CustomError::Paused
+        constraint = !protocol.is_paused(PAUSE_BET_INTAKE) @ CustomError::Paused
     )]
     pub protocol: Box<Account<'info, Protocol>>,
 
     #[account(
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = !pool.is_cancelled @ CustomError::PoolCancelled
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -32,18 +39,38 @@ pub struct InitBet<'info> {
     )]
     pub pool_vault: Box<Account<'info, TokenAccount>>,
 
+    /// Not required for native SOL pools, where the stake is wrapped straight into the vault.
     #[account(mut)]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Option<Box<Account<'info, TokenAccount>>>,
 
     #[account(
         init,
         payer = user,
-        space = Bet::SPACE,
-        seeds = [SEED_BET, pool.key().as_ref(), user.key().as_ref(), request_id.as_bytes()], 
+        space = Bet::LEN,
+        seeds = [
+            SEED_BET,
+            pool.key().as_ref(),
+            bet_owner.as_ref().map_or(user.key(), |owner| owner.key()).as_ref(),
+            request_id.as_bytes()
+        ],
         bump
     )]
     pub bet: Box<Account<'info, Bet>>,
 
+    /// Required when `referrer` is set; tracks that referrer's stats for this mint.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ReferrerAccount::LEN,
+        seeds = [
+            SEED_REFERRER,
+            referrer.unwrap_or_default().as_ref(),
+            pool.stake_token_mint.as_ref()
+        ],
+        bump
+    )]
+    pub referrer_account: Option<Box<Account<'info, ReferrerAccount>>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -53,31 +80,88 @@ pub fn init_bet(
     ctx: Context<InitBet>,
     amount: u64,
     _request_id: String, 
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
     let pool_key = ctx.accounts.pool.key();
     let pool = &mut ctx.accounts.pool;
     let clock = Clock::get()?;
 
-    require!(clock.unix_timestamp >= pool.start_time, CustomError::DurationTooShort);
-    require!(clock.unix_timestamp < pool.end_time, CustomError::DurationTooShort); 
+    require!(
+        clock.unix_timestamp >= pool.start_time && clock.unix_timestamp < pool.end_time,
+        CustomError::BettingWindowClosed
+    );
+    require!(
+        referrer != Some(ctx.accounts.user.key()),
+        CustomError::InvalidReferrer
+    );
+
+    if pool.is_native() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.pool_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
 
-    token::transfer(
-        CpiContext::new(
+        token::sync_native(CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_token_account.to_account_info(),
-                to: ctx.accounts.pool_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.pool_vault.to_account_info(),
             },
-        ),
-        amount,
-    )?;
+        ))?;
+    } else {
+        let user_token_account = ctx
+            .accounts
+            .user_token_account
+            .as_ref()
+            .ok_or(CustomError::MissingTokenAccount)?;
 
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: user_token_account.to_account_info(),
+                    to: ctx.accounts.pool_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    // Stake paid in here is public through the vault transfer, so it is counted
+    // on the pool even with private aggregates; only shielded volume is hidden.
     pool.total_volume = pool.total_volume.checked_add(amount).unwrap();
-    pool.total_participants = pool.total_participants.checked_add(1).unwrap();
+    // Pools with private aggregates count participants inside the rollup.
+    if !pool.private_aggregates {
+        pool.total_participants = pool.total_participants.checked_add(1).unwrap();
+    }
+    if let Some(referrer) = referrer {
+        pool.referred_volume = pool.referred_volume.checked_add(amount).unwrap();
+
+        let referrer_account = ctx
+            .accounts
+            .referrer_account
+            .as_mut()
+            .ok_or(CustomError::MissingReferrerAccount)?;
+        referrer_account.referrer = referrer;
+        referrer_account.mint = pool.stake_token_mint;
+        referrer_account.bump = ctx.bumps.referrer_account.ok_or(CustomError::MissingReferrerAccount)?;
+        referrer_account.referred_bets = referrer_account.referred_bets.checked_add(1).unwrap();
+        referrer_account.referred_volume = referrer_account.referred_volume.checked_add(amount).unwrap();
+    }
 
     let bet = &mut ctx.accounts.bet;
-    bet.user_pubkey = ctx.accounts.user.key();
+    // A one-time `bet_owner` keeps the funding wallet off the bet itself.
+    bet.user_pubkey = ctx
+        .accounts
+        .bet_owner
+        .as_ref()
+        .map_or(ctx.accounts.user.key(), |owner| owner.key());
     bet.pool_pubkey = pool_key;
     bet.stake = amount; 
     bet.end_timestamp = pool.end_time;
@@ -89,6 +173,11 @@ pub fn init_bet(
     bet.status = BetStatus::Pending;
     bet.prediction = 0; 
     bet.bump = ctx.bumps.bet;
+    bet.referrer = referrer;
+    bet.version = Bet::CURRENT_VERSION;
+    bet.shielded_stake = 0;
+    bet.referral_credited = false;
+    bet.compliance_viewer = Pubkey::default();
 
     msg!("Bet Initialized on L1. Funds Secured.");
 
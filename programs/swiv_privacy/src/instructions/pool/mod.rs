@@ -6,6 +6,12 @@ pub mod claim_reward;
 pub mod update_bet;
 pub mod init_bet;
 pub mod emergency_refund;
+pub mod cancel_pool;
+pub mod settle_creator_bond;
+pub mod reclaim_seed_subsidy;
+pub mod sponsor_rewards;
+pub mod claim_referral_rewards;
+pub mod credit_referral_fees;
 
 pub use create_pool::*;
 pub use place_bet::*;
@@ -14,4 +20,10 @@ pub use finalize_weights::*;
 pub use claim_reward::*;
 pub use update_bet::*;
 pub use init_bet::*;
-pub use emergency_refund::*;
\ No newline at end of file
+pub use emergency_refund::*;
+pub use cancel_pool::*;
+pub use settle_creator_bond::*;
+pub use reclaim_seed_subsidy::*;
+pub use sponsor_rewards::*;
+pub use claim_referral_rewards::*;
+pub use credit_referral_fees::*;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{Pool, Bet, BetStatus};
-use crate::constants::{SEED_BET, SEED_POOL}; 
+use crate::state::{Pool, PoolAggregates, Bet, BetStatus, Protocol};
+use crate::constants::{SEED_BET, SEED_POOL, SEED_POOL_AGGREGATES, SEED_PROTOCOL, PAUSE_BET_INTAKE}; 
 use crate::errors::CustomError;
 use crate::events::BetPlaced;
 
@@ -10,9 +10,18 @@ pub struct PlaceBet<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_BET_INTAKE) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
     #[account(
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = !pool.is_cancelled @ CustomError::PoolCancelled
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -23,6 +32,14 @@ pub struct PlaceBet<'info> {
         constraint = bet.user_pubkey == user.key() @ CustomError::Unauthorized
     )]
     pub bet: Box<Account<'info, Bet>>,
+
+    /// Required for pools with private aggregates, which count participants here.
+    #[account(
+        mut,
+        seeds = [SEED_POOL_AGGREGATES, pool.key().as_ref()],
+        bump = pool_aggregates.bump
+    )]
+    pub pool_aggregates: Option<Box<Account<'info, PoolAggregates>>>,
 }
 
 pub fn place_bet(
@@ -39,11 +56,20 @@ pub fn place_bet(
     bet.status = BetStatus::Active;
     bet.update_count = bet.update_count.checked_add(1).unwrap();
 
+    if pool.private_aggregates {
+        let aggregates = ctx
+            .accounts
+            .pool_aggregates
+            .as_mut()
+            .ok_or(CustomError::MissingPoolAggregates)?;
+        aggregates.total_participants = aggregates.total_participants.checked_add(1).unwrap();
+    }
+
     emit!(BetPlaced {
         bet_address: bet.key(),
         user: ctx.accounts.user.key(),
         pool_identifier: pool.title.clone(),
-        amount: bet.stake,
+        amount: pool.reveals_stakes().then_some(bet.stake),
         end_timestamp: pool.end_time,
     });
 
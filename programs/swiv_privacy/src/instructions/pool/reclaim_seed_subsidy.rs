@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Pool;
+use crate::constants::{BOND_RESOLUTION_WINDOW, SEED_POOL, SEED_POOL_VAULT};
+use crate::errors::CustomError;
+use crate::events::SeedSubsidyReclaimed;
+
+#[derive(Accounts)]
+pub struct ReclaimSeedSubsidy<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = pool.created_by == creator.key() @ CustomError::Unauthorized,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns the seed subsidy when nobody can win it: the pool was cancelled,
+/// it was finalized without any winning weight, or it is still unresolved
+/// once the resolution window after `end_time` has run out.
+pub fn reclaim_seed_subsidy(ctx: Context<ReclaimSeedSubsidy>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.seed_subsidy > 0 && !pool.subsidy_reclaimed,
+        CustomError::SubsidyNotReclaimable
+    );
+    let resolution_abandoned = !pool.is_resolved
+        && clock.unix_timestamp > pool.end_time.saturating_add(BOND_RESOLUTION_WINDOW);
+    require!(
+        pool.is_cancelled
+            || (pool.weight_finalized && pool.total_weight == 0)
+            || resolution_abandoned,
+        CustomError::SubsidyNotReclaimable
+    );
+
+    let amount = pool.seed_subsidy;
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_vault.to_account_info(),
+                to: ctx.accounts.creator_token_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    pool.subsidy_reclaimed = true;
+
+    emit!(SeedSubsidyReclaimed {
+        pool_address: pool.key(),
+        creator: pool.created_by,
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{Pool, Protocol};
-use crate::constants::{SEED_PROTOCOL, SEED_POOL};
+use crate::state::{Pool, Protocol, Role};
+use crate::constants::{SEED_PROTOCOL, SEED_POOL, PAUSE_SETTLEMENT};
 use crate::errors::CustomError;
 use crate::events::PoolResolved;
 
@@ -12,22 +12,28 @@ pub struct ResolvePool<'info> {
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+        constraint = !protocol.is_paused(PAUSE_SETTLEMENT) @ CustomError::Paused
     )]
     pub protocol: Account<'info, Protocol>,
 
+    /// Only a resolver may pick the outcome; creators can bet on their own pools.
     #[account(
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = protocol.has_role(Role::Resolver, &admin.key()) @ CustomError::Unauthorized
     )]
     pub pool: Account<'info, Pool>,
 }
 
 pub fn resolve_pool(ctx: Context<ResolvePool>, final_outcome: u64) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-    
+    apply_resolution(&mut ctx.accounts.pool, final_outcome)
+}
+
+pub(crate) fn apply_resolution(pool: &mut Pool, final_outcome: u64) -> Result<()> {
     require!(!pool.is_resolved, CustomError::AlreadyClaimed);
+    require!(!pool.is_cancelled, CustomError::PoolCancelled);
     
     let clock = Clock::get()?;
     require!(clock.unix_timestamp >= pool.end_time, CustomError::DurationTooShort);
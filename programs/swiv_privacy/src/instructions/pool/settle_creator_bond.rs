@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{FeeLedger, Pool, Protocol};
+use crate::constants::{
+    BOND_RESOLUTION_WINDOW, PAUSE_SETTLEMENT, SEED_BOND_VAULT, SEED_FEE_LEDGER, SEED_FEE_VAULT,
+    SEED_POOL, SEED_PROTOCOL,
+};
+use crate::errors::CustomError;
+use crate::events::CreatorBondSettled;
+
+#[derive(Accounts)]
+pub struct SettleCreatorBond<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_SETTLEMENT) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = pool.creator_bond > 0 @ CustomError::MissingBondVault,
+        constraint = !pool.bond_settled @ CustomError::BondAlreadySettled
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = pool.stake_token_mint)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeLedger::LEN,
+        seeds = [SEED_FEE_LEDGER, token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [SEED_FEE_VAULT, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Required when the bond is returned.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Permissionless. Returns the bond once the pool is resolved or cancelled
+/// without fault, and slashes it into the fee vault if the pool was voided
+/// for creator fault or left unresolved past `BOND_RESOLUTION_WINDOW`.
+pub fn settle_creator_bond(ctx: Context<SettleCreatorBond>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let slashed = if pool.is_resolved || (pool.is_cancelled && !pool.cancelled_for_creator_fault) {
+        false
+    } else if pool.is_cancelled
+        || clock.unix_timestamp > pool.end_time.saturating_add(BOND_RESOLUTION_WINDOW)
+    {
+        true
+    } else {
+        return err!(CustomError::BondNotSettleable);
+    };
+
+    let amount = pool.creator_bond;
+    let destination = if slashed {
+        ctx.accounts.fee_vault.to_account_info()
+    } else {
+        ctx.accounts
+            .creator_token_account
+            .as_ref()
+            .ok_or(CustomError::MissingTokenAccount)?
+            .to_account_info()
+    };
+
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: destination,
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    if slashed {
+        let ledger = &mut ctx.accounts.fee_ledger;
+        ledger.mint = ctx.accounts.token_mint.key();
+        ledger.bump = ctx.bumps.fee_ledger;
+        ledger.total_accrued = ledger.total_accrued.checked_add(amount).unwrap();
+    }
+
+    pool.bond_settled = true;
+
+    emit!(CreatorBondSettled {
+        pool_address: pool.key(),
+        creator: pool.created_by,
+        amount,
+        slashed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{Bet, BetStatus, Pool, Protocol, SponsorClaim, SponsorReward};
+use crate::constants::{
+    PAUSE_CLAIMS, SEED_POOL, SEED_PROTOCOL, SEED_SPONSOR_CLAIM, SEED_SPONSOR_REWARD,
+    SEED_SPONSOR_VAULT,
+};
+use crate::errors::CustomError;
+use crate::events::{PoolSponsored, SponsorRewardClaimed, SponsorRewardRefunded};
+use crate::utils::signature::authorize_bet_payout;
+
+fn pay_from_sponsor_vault<'info>(
+    token_program: &Program<'info, Token>,
+    pool: &Account<'info, Pool>,
+    sponsor_vault: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: sponsor_vault.to_account_info(),
+                to,
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+pub struct SponsorPool<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = !pool.is_cancelled @ CustomError::PoolCancelled,
+        constraint = !pool.is_resolved @ CustomError::SponsorshipClosed
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = SponsorReward::LEN,
+        seeds = [
+            SEED_SPONSOR_REWARD,
+            pool.key().as_ref(),
+            reward_mint.key().as_ref(),
+            sponsor.key().as_ref()
+        ],
+        bump
+    )]
+    pub sponsor_reward: Box<Account<'info, SponsorReward>>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        seeds = [SEED_SPONSOR_VAULT, sponsor_reward.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = pool,
+    )]
+    pub sponsor_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = sponsor,
+    )]
+    pub sponsor_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Adds `amount` of `reward_mint` to the pool's rewards. Every sponsor gets
+/// its own reward per mint, which it may top up until the pool is resolved.
+pub fn sponsor_pool(ctx: Context<SponsorPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, CustomError::InvalidAmount);
+
+    let sponsor = ctx.accounts.sponsor.key();
+    let sponsor_reward = &mut ctx.accounts.sponsor_reward;
+
+    if sponsor_reward.sponsor == Pubkey::default() {
+        sponsor_reward.pool = ctx.accounts.pool.key();
+        sponsor_reward.mint = ctx.accounts.reward_mint.key();
+        sponsor_reward.sponsor = sponsor;
+        sponsor_reward.bump = ctx.bumps.sponsor_reward;
+    }
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.sponsor_token_account.to_account_info(),
+                to: ctx.accounts.sponsor_vault.to_account_info(),
+                authority: ctx.accounts.sponsor.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    sponsor_reward.total_amount = sponsor_reward.total_amount.checked_add(amount).unwrap();
+
+    emit!(PoolSponsored {
+        pool_address: sponsor_reward.pool,
+        sponsor,
+        mint: sponsor_reward.mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimSponsorReward<'info> {
+    /// Either the bet owner, or anyone relaying a claim the owner signed for
+    /// `user_token_account`.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_CLAIMS) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        constraint = bet.pool_pubkey == pool.key() @ CustomError::PoolMismatch,
+        constraint = (bet.status == BetStatus::Resolved || bet.status == BetStatus::Claimed)
+            @ CustomError::NotCalculatedYet
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SPONSOR_REWARD,
+            pool.key().as_ref(),
+            sponsor_reward.mint.as_ref(),
+            sponsor_reward.sponsor.as_ref()
+        ],
+        bump = sponsor_reward.bump
+    )]
+    pub sponsor_reward: Box<Account<'info, SponsorReward>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SPONSOR_VAULT, sponsor_reward.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub sponsor_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Created here so the same bet cannot claim this reward twice.
+    #[account(
+        init,
+        payer = user,
+        space = SponsorClaim::LEN,
+        seeds = [SEED_SPONSOR_CLAIM, sponsor_reward.key().as_ref(), bet.key().as_ref()],
+        bump
+    )]
+    pub sponsor_claim: Box<Account<'info, SponsorClaim>>,
+
+    #[account(
+        mut,
+        token::mint = sponsor_reward.mint
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Instructions sysvar, required when the bet owner authorizes the claim by signature.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays the bet's `calculated_weight / total_weight` share of one sponsor reward.
+pub fn claim_sponsor_reward(ctx: Context<ClaimSponsorReward>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let bet = &ctx.accounts.bet;
+
+    require!(pool.weight_finalized, CustomError::SettlementTooEarly);
+    require!(!ctx.accounts.sponsor_reward.refunded, CustomError::AlreadyClaimed);
+    // Emergency refunds also mark bets claimed, but those were never weighted.
+    require!(bet.calculated_weight > 0, CustomError::NoWinningWeight);
+    authorize_bet_payout(
+        &bet.user_pubkey,
+        &ctx.accounts.user.key(),
+        &bet.key(),
+        &ctx.accounts.user_token_account.key(),
+        ctx.accounts.instructions_sysvar.as_ref().map(|ix| ix.as_ref()),
+    )?;
+
+    let mut amount: u64 = 0;
+    if pool.total_weight > 0 {
+        amount = bet
+            .calculated_weight
+            .checked_mul(ctx.accounts.sponsor_reward.total_amount as u128)
+            .unwrap()
+            .checked_div(pool.total_weight)
+            .unwrap() as u64;
+    }
+
+    if amount > 0 {
+        pay_from_sponsor_vault(
+            &ctx.accounts.token_program,
+            pool,
+            &ctx.accounts.sponsor_vault,
+            ctx.accounts.user_token_account.to_account_info(),
+            amount,
+        )?;
+    }
+
+    let sponsor_reward = &mut ctx.accounts.sponsor_reward;
+    sponsor_reward.total_claimed = sponsor_reward.total_claimed.checked_add(amount).unwrap();
+
+    let sponsor_claim = &mut ctx.accounts.sponsor_claim;
+    sponsor_claim.sponsor_reward = sponsor_reward.key();
+    sponsor_claim.bet = bet.key();
+    sponsor_claim.amount = amount;
+    sponsor_claim.bump = ctx.bumps.sponsor_claim;
+
+    emit!(SponsorRewardClaimed {
+        bet_address: bet.key(),
+        user: ctx.accounts.user.key(),
+        mint: sponsor_reward.mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundSponsorReward<'info> {
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_SPONSOR_REWARD,
+            pool.key().as_ref(),
+            sponsor_reward.mint.as_ref(),
+            sponsor_reward.sponsor.as_ref()
+        ],
+        bump = sponsor_reward.bump,
+        constraint = sponsor_reward.sponsor == sponsor.key() @ CustomError::Unauthorized,
+        constraint = !sponsor_reward.refunded @ CustomError::AlreadyClaimed
+    )]
+    pub sponsor_reward: Box<Account<'info, SponsorReward>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SPONSOR_VAULT, sponsor_reward.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub sponsor_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = sponsor_reward.mint,
+        token::authority = sponsor,
+    )]
+    pub sponsor_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns a sponsor reward nobody can win: the pool was cancelled, or it was
+/// finalized without any winning weight.
+pub fn refund_sponsor_reward(ctx: Context<RefundSponsorReward>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(
+        pool.is_cancelled || (pool.weight_finalized && pool.total_weight == 0),
+        CustomError::SponsorRewardNotRefundable
+    );
+
+    let amount = ctx.accounts.sponsor_vault.amount;
+    if amount > 0 {
+        pay_from_sponsor_vault(
+            &ctx.accounts.token_program,
+            pool,
+            &ctx.accounts.sponsor_vault,
+            ctx.accounts.sponsor_token_account.to_account_info(),
+            amount,
+        )?;
+    }
+
+    let sponsor_reward = &mut ctx.accounts.sponsor_reward;
+    sponsor_reward.refunded = true;
+
+    emit!(SponsorRewardRefunded {
+        pool_address: pool.key(),
+        sponsor: sponsor_reward.sponsor,
+        mint: sponsor_reward.mint,
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{Bet, Pool, BetStatus};
-use crate::constants::{SEED_POOL};
+use crate::state::{Bet, Pool, BetStatus, Protocol};
+use crate::constants::{SEED_POOL, SEED_PROTOCOL, PAUSE_UPDATES};
 use crate::errors::CustomError;
 use crate::events::BetUpdated;
 
@@ -9,6 +9,13 @@ pub struct UpdateBet<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_UPDATES) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
     #[account(
         mut,
         constraint = bet.user_pubkey == user.key() @ CustomError::Unauthorized,
@@ -18,7 +25,9 @@ pub struct UpdateBet<'info> {
 
     #[account(
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = !pool.is_cancelled @ CustomError::PoolCancelled
     )]
     pub pool: Box<Account<'info, Pool>>,
 }
@@ -33,7 +42,11 @@ pub fn update_bet(
     bet.update_count = bet.update_count.checked_add(1).unwrap();
     bet.prediction = new_prediction;
     
-    msg!("Bet Updated securely via TEE. New prediction stored: {}", new_prediction);
+    if pool.reveals_predictions() {
+        msg!("Bet Updated securely via TEE. New prediction stored: {}", new_prediction);
+    } else {
+        msg!("Bet Updated securely via TEE.");
+    }
 
     emit!(BetUpdated {
         bet_address: bet.key(),
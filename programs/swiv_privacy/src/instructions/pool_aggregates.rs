@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, PoolAggregates, Protocol, Role};
+use crate::constants::{SEED_POOL, SEED_POOL_AGGREGATES, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::{PoolAggregatesCreated, PoolAggregatesSynced};
+
+#[derive(Accounts)]
+pub struct CreatePoolAggregates<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = pool.created_by == authority.key()
+            || protocol.has_role(Role::PoolCreator, &authority.key()) @ CustomError::Unauthorized
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PoolAggregates::LEN,
+        seeds = [SEED_POOL_AGGREGATES, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_aggregates: Box<Account<'info, PoolAggregates>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves the pool's open-period totals into a separate account that is then
+/// permissioned and delegated to the rollup. Must happen before betting opens
+/// so every participant is counted in the same place.
+pub fn create_pool_aggregates(ctx: Context<CreatePoolAggregates>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(clock.unix_timestamp < pool.start_time, CustomError::DurationTooShort);
+
+    pool.private_aggregates = true;
+    pool.aggregates_synced = false;
+
+    let aggregates = &mut ctx.accounts.pool_aggregates;
+    aggregates.pool = pool.key();
+    aggregates.total_volume = 0;
+    aggregates.shielded_volume = 0;
+    aggregates.referred_volume = 0;
+    aggregates.total_participants = 0;
+    aggregates.synced = false;
+    aggregates.bump = ctx.bumps.pool_aggregates;
+
+    emit!(PoolAggregatesCreated {
+        pool_address: pool.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncPoolAggregates<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.aggregates_synced @ CustomError::AlreadyClaimed
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_AGGREGATES, pool.key().as_ref()],
+        bump = pool_aggregates.bump,
+    )]
+    pub pool_aggregates: Box<Account<'info, PoolAggregates>>,
+}
+
+/// Permissionless. Once betting has closed and the aggregates are back on L1,
+/// folds them into the public pool.
+pub fn sync_pool_aggregates(ctx: Context<SyncPoolAggregates>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let aggregates = &mut ctx.accounts.pool_aggregates;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.is_cancelled || clock.unix_timestamp >= pool.end_time,
+        CustomError::SettlementTooEarly
+    );
+
+    pool.total_volume = pool.total_volume.checked_add(aggregates.total_volume).unwrap();
+    pool.shielded_volume = pool.shielded_volume.checked_add(aggregates.shielded_volume).unwrap();
+    pool.referred_volume = pool.referred_volume.checked_add(aggregates.referred_volume).unwrap();
+    pool.total_participants = pool
+        .total_participants
+        .checked_add(aggregates.total_participants)
+        .unwrap();
+    pool.aggregates_synced = true;
+    aggregates.synced = true;
+
+    emit!(PoolAggregatesSynced {
+        pool_address: pool.key(),
+        total_volume: aggregates.total_volume,
+        total_participants: aggregates.total_participants,
+    });
+
+    Ok(())
+}
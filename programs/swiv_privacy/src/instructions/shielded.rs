@@ -0,0 +1,631 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{
+    Bet, BetStatus, DepositPool, Pool, PoolAggregates, Protocol, Role, ShieldedBalance,
+};
+use crate::constants::{
+    PAUSE_BET_INTAKE, SEED_BET, SEED_DEPOSIT_POOL, SEED_DEPOSIT_VAULT, SEED_POOL,
+    SEED_POOL_AGGREGATES, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_SHIELDED_BALANCE,
+};
+use crate::errors::CustomError;
+use crate::events::{
+    DepositPoolInitialized, ShieldedDeposit, ShieldedStakeSettled, ShieldedWithdrawal,
+};
+
+fn pay_from_deposit_vault<'info>(
+    token_program: &Program<'info, Token>,
+    deposit_pool: &Account<'info, DepositPool>,
+    deposit_vault: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let mint = deposit_pool.mint;
+    let seeds = &[SEED_DEPOSIT_POOL, mint.as_ref(), &[deposit_pool.bump]];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: deposit_vault.to_account_info(),
+                to,
+                authority: deposit_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+/// Shielded stake can only change while the pool is open for betting and
+/// before the bet has been weighed.
+fn check_stake_adjustable(pool: &Pool, bet: &Bet) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= pool.start_time && clock.unix_timestamp < pool.end_time,
+        CustomError::BettingWindowClosed
+    );
+    require!(
+        !bet.is_weight_added && bet.status != BetStatus::Resolved,
+        CustomError::StakeLocked
+    );
+    Ok(())
+}
+
+/// Stake drawn from or returned to a shielded balance must be whole notes, so
+/// balances stay withdrawable through `withdraw_shielded`.
+#[allow(clippy::manual_is_multiple_of)]
+fn check_whole_notes(deposit_pool: &DepositPool, amount: u64) -> Result<()> {
+    require!(amount % deposit_pool.denomination == 0, CustomError::PartialNote);
+    Ok(())
+}
+
+fn add_shielded_stake(aggregates: &mut PoolAggregates, bet: &mut Bet, amount: u64) {
+    bet.stake = bet.stake.checked_add(amount).unwrap();
+    bet.shielded_stake = bet.shielded_stake.checked_add(amount).unwrap();
+
+    aggregates.total_volume = aggregates.total_volume.checked_add(amount).unwrap();
+    aggregates.shielded_volume = aggregates.shielded_volume.checked_add(amount).unwrap();
+    if bet.referrer.is_some() {
+        aggregates.referred_volume = aggregates.referred_volume.checked_add(amount).unwrap();
+    }
+}
+
+fn remove_shielded_stake(aggregates: &mut PoolAggregates, bet: &mut Bet, amount: u64) -> Result<()> {
+    require!(
+        bet.shielded_stake >= amount,
+        CustomError::InsufficientShieldedBalance
+    );
+    bet.stake -= amount;
+    bet.shielded_stake -= amount;
+
+    aggregates.total_volume = aggregates.total_volume.checked_sub(amount).unwrap();
+    aggregates.shielded_volume = aggregates.shielded_volume.checked_sub(amount).unwrap();
+    if bet.referrer.is_some() {
+        aggregates.referred_volume = aggregates.referred_volume.checked_sub(amount).unwrap();
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeDepositPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.has_role(Role::PoolCreator, &admin.key()) @ CustomError::Unauthorized
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = DepositPool::LEN,
+        seeds = [SEED_DEPOSIT_POOL, token_mint.key().as_ref()],
+        bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SEED_DEPOSIT_VAULT, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = deposit_pool,
+    )]
+    pub deposit_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_deposit_pool(
+    ctx: Context<InitializeDepositPool>,
+    denomination: u64,
+) -> Result<()> {
+    require!(denomination > 0, CustomError::InvalidDenomination);
+
+    let deposit_pool = &mut ctx.accounts.deposit_pool;
+    deposit_pool.mint = ctx.accounts.token_mint.key();
+    deposit_pool.denomination = denomination;
+    deposit_pool.total_deposited = 0;
+    deposit_pool.total_withdrawn = 0;
+    deposit_pool.total_settled = 0;
+    deposit_pool.bump = ctx.bumps.deposit_pool;
+
+    emit!(DepositPoolInitialized {
+        mint: deposit_pool.mint,
+        denomination,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositShielded<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_DEPOSIT_POOL, deposit_pool.mint.as_ref()],
+        bump = deposit_pool.bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_DEPOSIT_VAULT, deposit_pool.mint.as_ref()],
+        bump,
+        token::authority = deposit_pool,
+    )]
+    pub deposit_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ShieldedBalance::LEN,
+        seeds = [SEED_SHIELDED_BALANCE, deposit_pool.mint.as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub shielded_balance: Box<Account<'info, ShieldedBalance>>,
+
+    #[account(
+        mut,
+        token::mint = deposit_pool.mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits `notes` fixed-size notes. The balance must be on L1, i.e. not
+/// currently delegated to the rollup.
+pub fn deposit_shielded(ctx: Context<DepositShielded>, notes: u64) -> Result<()> {
+    let deposit_pool = &mut ctx.accounts.deposit_pool;
+    let amount = notes
+        .checked_mul(deposit_pool.denomination)
+        .ok_or(CustomError::MathOverflow)?;
+    require!(amount > 0, CustomError::InvalidAmount);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                to: ctx.accounts.deposit_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    deposit_pool.total_deposited = deposit_pool.total_deposited.checked_add(amount).unwrap();
+
+    let shielded_balance = &mut ctx.accounts.shielded_balance;
+    shielded_balance.owner = ctx.accounts.owner.key();
+    shielded_balance.mint = deposit_pool.mint;
+    shielded_balance.bump = ctx.bumps.shielded_balance;
+    shielded_balance.balance = shielded_balance.balance.checked_add(amount).unwrap();
+
+    emit!(ShieldedDeposit {
+        mint: deposit_pool.mint,
+        notes,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawShielded<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_DEPOSIT_POOL, deposit_pool.mint.as_ref()],
+        bump = deposit_pool.bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_DEPOSIT_VAULT, deposit_pool.mint.as_ref()],
+        bump,
+        token::authority = deposit_pool,
+    )]
+    pub deposit_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SHIELDED_BALANCE, deposit_pool.mint.as_ref(), owner.key().as_ref()],
+        bump = shielded_balance.bump
+    )]
+    pub shielded_balance: Box<Account<'info, ShieldedBalance>>,
+
+    #[account(
+        mut,
+        token::mint = deposit_pool.mint,
+    )]
+    pub recipient_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraws `notes` fixed-size notes to any token account of the mint.
+pub fn withdraw_shielded(ctx: Context<WithdrawShielded>, notes: u64) -> Result<()> {
+    let amount = notes
+        .checked_mul(ctx.accounts.deposit_pool.denomination)
+        .ok_or(CustomError::MathOverflow)?;
+    require!(amount > 0, CustomError::InvalidAmount);
+
+    let shielded_balance = &mut ctx.accounts.shielded_balance;
+    require!(
+        shielded_balance.balance >= amount,
+        CustomError::InsufficientShieldedBalance
+    );
+    shielded_balance.balance -= amount;
+
+    pay_from_deposit_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.deposit_pool,
+        &ctx.accounts.deposit_vault,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        amount,
+    )?;
+
+    let deposit_pool = &mut ctx.accounts.deposit_pool;
+    deposit_pool.total_withdrawn = deposit_pool.total_withdrawn.checked_add(amount).unwrap();
+
+    emit!(ShieldedWithdrawal {
+        mint: deposit_pool.mint,
+        notes,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, request_id: String)]
+pub struct AllocateShieldedStake<'info> {
+    /// Owner of the shielded balance the stake is drawn from.
+    pub owner: Signer<'info>,
+
+    /// The bet's owner, which may be a one-time key distinct from `owner`.
+    pub bet_owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_BET_INTAKE) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        seeds = [SEED_DEPOSIT_POOL, pool.stake_token_mint.as_ref()],
+        bump = deposit_pool.bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SHIELDED_BALANCE, pool.stake_token_mint.as_ref(), owner.key().as_ref()],
+        bump = shielded_balance.bump
+    )]
+    pub shielded_balance: Box<Account<'info, ShieldedBalance>>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = !pool.is_cancelled @ CustomError::PoolCancelled
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_AGGREGATES, pool.key().as_ref()],
+        bump = pool_aggregates.bump,
+        constraint = !pool_aggregates.synced @ CustomError::AggregatesNotSynced
+    )]
+    pub pool_aggregates: Box<Account<'info, PoolAggregates>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BET, pool.key().as_ref(), bet_owner.key().as_ref(), request_id.as_bytes()],
+        bump = bet.bump,
+        constraint = bet.user_pubkey == bet_owner.key() @ CustomError::Unauthorized,
+        constraint = bet.status != BetStatus::Claimed @ CustomError::AlreadyClaimed
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+}
+
+/// Runs inside the rollup with the balance, pool, its aggregates and the bet
+/// delegated. Moves `amount`, a whole number of notes, from the shielded
+/// balance into the bet's stake with no L1 transfer; the bet is opened on L1 with `init_bet(0, ..)`,
+/// possibly under a one-time owner key.
+pub fn allocate_shielded_stake(
+    ctx: Context<AllocateShieldedStake>,
+    amount: u64,
+    _request_id: String,
+) -> Result<()> {
+    check_stake_adjustable(&ctx.accounts.pool, &ctx.accounts.bet)?;
+    require!(amount > 0, CustomError::InvalidAmount);
+    check_whole_notes(&ctx.accounts.deposit_pool, amount)?;
+
+    let shielded_balance = &mut ctx.accounts.shielded_balance;
+    require!(
+        shielded_balance.balance >= amount,
+        CustomError::InsufficientShieldedBalance
+    );
+    shielded_balance.balance -= amount;
+
+    add_shielded_stake(&mut ctx.accounts.pool_aggregates, &mut ctx.accounts.bet, amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: String, new_shielded_stake: u64)]
+pub struct ResizeShieldedStake<'info> {
+    /// Owner of the shielded balance the difference is drawn from or returned to.
+    pub owner: Signer<'info>,
+
+    /// The bet's owner, which may be a one-time key distinct from `owner`.
+    pub bet_owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_BET_INTAKE) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        seeds = [SEED_DEPOSIT_POOL, pool.stake_token_mint.as_ref()],
+        bump = deposit_pool.bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SHIELDED_BALANCE, pool.stake_token_mint.as_ref(), owner.key().as_ref()],
+        bump = shielded_balance.bump
+    )]
+    pub shielded_balance: Box<Account<'info, ShieldedBalance>>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.frozen @ CustomError::PoolFrozen,
+        constraint = !pool.is_cancelled @ CustomError::PoolCancelled
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_AGGREGATES, pool.key().as_ref()],
+        bump = pool_aggregates.bump,
+        constraint = !pool_aggregates.synced @ CustomError::AggregatesNotSynced
+    )]
+    pub pool_aggregates: Box<Account<'info, PoolAggregates>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BET, pool.key().as_ref(), bet_owner.key().as_ref(), request_id.as_bytes()],
+        bump = bet.bump,
+        constraint = bet.user_pubkey == bet_owner.key() @ CustomError::Unauthorized
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+}
+
+/// Runs inside the rollup. Sets the bet's shielded stake to
+/// `new_shielded_stake`, settling the difference, which must be whole notes,
+/// against the shielded balance.
+/// Stake paid in on L1 through `init_bet` is left untouched.
+pub fn resize_shielded_stake(
+    ctx: Context<ResizeShieldedStake>,
+    _request_id: String,
+    new_shielded_stake: u64,
+) -> Result<()> {
+    let aggregates = &mut ctx.accounts.pool_aggregates;
+    let bet = &mut ctx.accounts.bet;
+    let shielded_balance = &mut ctx.accounts.shielded_balance;
+    check_stake_adjustable(&ctx.accounts.pool, bet)?;
+    check_whole_notes(
+        &ctx.accounts.deposit_pool,
+        new_shielded_stake.abs_diff(bet.shielded_stake),
+    )?;
+
+    let current = bet.shielded_stake;
+    if new_shielded_stake > current {
+        let amount = new_shielded_stake - current;
+        require!(
+            shielded_balance.balance >= amount,
+            CustomError::InsufficientShieldedBalance
+        );
+        shielded_balance.balance -= amount;
+        add_shielded_stake(aggregates, bet, amount);
+    } else {
+        let amount = current - new_shielded_stake;
+        remove_shielded_stake(aggregates, bet, amount)?;
+        shielded_balance.balance = shielded_balance.balance.checked_add(amount).unwrap();
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, from_request_id: String, to_request_id: String)]
+pub struct MoveShieldedStake<'info> {
+    pub bet_owner: Signer<'info>,
+
+    /// Owner of `to_bet`, which may be a different one-time key.
+    pub to_bet_owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.is_paused(PAUSE_BET_INTAKE) @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        seeds = [SEED_DEPOSIT_POOL, from_pool.stake_token_mint.as_ref()],
+        bump = deposit_pool.bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        seeds = [SEED_POOL, from_pool.created_by.as_ref(), &(from_pool.pool_id.to_le_bytes())],
+        bump = from_pool.bump,
+        constraint = !from_pool.frozen @ CustomError::PoolFrozen,
+        constraint = !from_pool.is_cancelled @ CustomError::PoolCancelled
+    )]
+    pub from_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_AGGREGATES, from_pool.key().as_ref()],
+        bump = from_aggregates.bump,
+        constraint = !from_aggregates.synced @ CustomError::AggregatesNotSynced
+    )]
+    pub from_aggregates: Box<Account<'info, PoolAggregates>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BET, from_pool.key().as_ref(), bet_owner.key().as_ref(), from_request_id.as_bytes()],
+        bump = from_bet.bump,
+        constraint = from_bet.user_pubkey == bet_owner.key() @ CustomError::Unauthorized
+    )]
+    pub from_bet: Box<Account<'info, Bet>>,
+
+    #[account(
+        seeds = [SEED_POOL, to_pool.created_by.as_ref(), &(to_pool.pool_id.to_le_bytes())],
+        bump = to_pool.bump,
+        constraint = to_pool.key() != from_pool.key() @ CustomError::PoolMismatch,
+        constraint = to_pool.stake_token_mint == from_pool.stake_token_mint @ CustomError::PoolMismatch,
+        constraint = !to_pool.frozen @ CustomError::PoolFrozen,
+        constraint = !to_pool.is_cancelled @ CustomError::PoolCancelled
+    )]
+    pub to_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_AGGREGATES, to_pool.key().as_ref()],
+        bump = to_aggregates.bump,
+        constraint = !to_aggregates.synced @ CustomError::AggregatesNotSynced
+    )]
+    pub to_aggregates: Box<Account<'info, PoolAggregates>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BET, to_pool.key().as_ref(), to_bet_owner.key().as_ref(), to_request_id.as_bytes()],
+        bump = to_bet.bump,
+        constraint = to_bet.user_pubkey == to_bet_owner.key() @ CustomError::Unauthorized,
+        constraint = to_bet.status != BetStatus::Claimed @ CustomError::AlreadyClaimed
+    )]
+    pub to_bet: Box<Account<'info, Bet>>,
+}
+
+/// Runs inside the rollup with both pools, their aggregates and bets delegated. Moves `amount`,
+/// a whole number of notes, of shielded stake from a bet in one pool to a bet in another
+/// without it passing through L1; each pool's aggregate is settled separately.
+pub fn move_shielded_stake(
+    ctx: Context<MoveShieldedStake>,
+    amount: u64,
+    _from_request_id: String,
+    _to_request_id: String,
+) -> Result<()> {
+    require!(amount > 0, CustomError::InvalidAmount);
+    check_whole_notes(&ctx.accounts.deposit_pool, amount)?;
+    check_stake_adjustable(&ctx.accounts.from_pool, &ctx.accounts.from_bet)?;
+    check_stake_adjustable(&ctx.accounts.to_pool, &ctx.accounts.to_bet)?;
+
+    remove_shielded_stake(&mut ctx.accounts.from_aggregates, &mut ctx.accounts.from_bet, amount)?;
+    add_shielded_stake(&mut ctx.accounts.to_aggregates, &mut ctx.accounts.to_bet, amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleShieldedStakes<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = !pool.shielded_settled @ CustomError::AlreadyClaimed
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_DEPOSIT_POOL, pool.stake_token_mint.as_ref()],
+        bump = deposit_pool.bump
+    )]
+    pub deposit_pool: Box<Account<'info, DepositPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_DEPOSIT_VAULT, pool.stake_token_mint.as_ref()],
+        bump,
+        token::authority = deposit_pool,
+    )]
+    pub deposit_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless. Once betting has closed and the pool is back on L1, moves
+/// the pool's aggregate shielded stake into its vault in a single transfer.
+pub fn settle_shielded_stakes(ctx: Context<SettleShieldedStakes>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.is_cancelled || clock.unix_timestamp >= pool.end_time,
+        CustomError::SettlementTooEarly
+    );
+    require!(pool.aggregates_settled(), CustomError::AggregatesNotSynced);
+
+    let amount = pool.shielded_volume;
+    if amount > 0 {
+        pay_from_deposit_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.deposit_pool,
+            &ctx.accounts.deposit_vault,
+            ctx.accounts.pool_vault.to_account_info(),
+            amount,
+        )?;
+    }
+
+    let deposit_pool = &mut ctx.accounts.deposit_pool;
+    deposit_pool.total_settled = deposit_pool.total_settled.checked_add(amount).unwrap();
+
+    let pool = &mut ctx.accounts.pool;
+    pool.shielded_settled = true;
+
+    emit!(ShieldedStakeSettled {
+        pool_address: pool.key(),
+        amount,
+    });
+
+    Ok(())
+}
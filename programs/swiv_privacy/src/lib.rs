@@ -9,6 +9,7 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::{ConfigParam, FeeMode, GovernanceAction, PrivacyPolicy};
 
 declare_id!("4RDfF1cC6WBGyQ1zhUNDkbPwMfSKjuCPXF3ygt6KmVwy");
 
@@ -25,25 +26,105 @@ pub mod swiv_privacy {
         admin::initialize_protocol(ctx, protocol_fee_bps)
     }
 
-    pub fn update_config(
-        ctx: Context<UpdateConfig>,
-        new_treasury: Option<Pubkey>,
-        new_protocol_fee_bps: Option<u64>,
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        change: ConfigParam,
     ) -> Result<()> {
-        admin::update_config(ctx, new_treasury, new_protocol_fee_bps)
+        admin::queue_config_change(ctx, change)
     }
 
-    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
-        admin::transfer_admin(ctx, new_admin)
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        admin::execute_config_change(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        admin::cancel_config_change(ctx)
+    }
+
+    pub fn migrate_protocol(ctx: Context<MigrateProtocol>) -> Result<()> {
+        admin::migrate_protocol(ctx)
+    }
+
+    pub fn migrate_pools<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAccounts<'info>>,
+    ) -> Result<()> {
+        admin::migrate_pools(ctx)
+    }
+
+    pub fn migrate_bets<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAccounts<'info>>,
+    ) -> Result<()> {
+        admin::migrate_bets(ctx)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        admin::accept_admin(ctx)
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        admin::cancel_admin_transfer(ctx)
     }
 
     pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
         admin::set_pause(ctx, paused)
     }
 
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        admin::set_pause_flags(ctx, pause_flags)
+    }
+
+    pub fn set_pool_freeze(ctx: Context<SetPoolFreeze>, frozen: bool) -> Result<()> {
+        admin::set_pool_freeze(ctx, frozen)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        admin::withdraw_fees(ctx, amount)
+    }
+
+    pub fn add_whitelisted_creator(ctx: Context<AddWhitelistedCreator>, creator: Pubkey) -> Result<()> {
+        admin::add_whitelisted_creator(ctx, creator)
+    }
+
+    pub fn remove_whitelisted_creator(ctx: Context<RemoveWhitelistedCreator>) -> Result<()> {
+        admin::remove_whitelisted_creator(ctx)
+    }
+
+    pub fn add_approved_validator(ctx: Context<AddApprovedValidator>, validator: Pubkey) -> Result<()> {
+        admin::add_approved_validator(ctx, validator)
+    }
+
+    pub fn remove_approved_validator(ctx: Context<RemoveApprovedValidator>) -> Result<()> {
+        admin::remove_approved_validator(ctx)
+    }
+
+    pub fn set_pool_validator(ctx: Context<SetPoolValidator>) -> Result<()> {
+        admin::set_pool_validator(ctx)
+    }
+
+    // --- GOVERNANCE ---
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::governance::initialize_governance(ctx, signers, threshold)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: GovernanceAction) -> Result<()> {
+        instructions::governance::create_proposal(ctx, action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::governance::approve_proposal(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        instructions::governance::execute_proposal(ctx)
+    }
+
     // --- DELEGATION ---
-    pub fn delegate_pool(ctx: Context<DelegatePool>, pool_id: u64) -> Result<()> {
-        instructions::delegation::delegate_pool(ctx, pool_id)
+    pub fn delegate_pool(ctx: Context<DelegatePool>) -> Result<()> {
+        instructions::delegation::delegate_pool(ctx)
     }
 
     pub fn undelegate_pool(ctx: Context<UndelegatePool>) -> Result<()> {
@@ -60,10 +141,45 @@ pub mod swiv_privacy {
         instructions::delegation::batch_undelegate_bets(ctx)
     }
 
+    pub fn batch_undelegate_bet_permissions<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchUndelegateBetPermissions<'info>>,
+        request_ids: Vec<String>,
+    ) -> Result<()> {
+        instructions::delegation::batch_undelegate_bet_permissions(ctx, request_ids)
+    }
+
     pub fn create_bet_permission(ctx: Context<CreateBetPermission>, req_id: String) -> Result<()> {
         instructions::permission::create_bet_permission(ctx, req_id)
     }
 
+    pub fn add_bet_viewer(
+        ctx: Context<UpdateBetViewers>,
+        request_id: String,
+        viewer: Pubkey,
+        flags: u8,
+    ) -> Result<()> {
+        instructions::permission::add_bet_viewer(ctx, request_id, viewer, flags)
+    }
+
+    pub fn remove_bet_viewer(
+        ctx: Context<UpdateBetViewers>,
+        request_id: String,
+        viewer: Pubkey,
+    ) -> Result<()> {
+        instructions::permission::remove_bet_viewer(ctx, request_id, viewer)
+    }
+
+    pub fn close_bet_permission(ctx: Context<CloseBetPermission>, request_id: String) -> Result<()> {
+        instructions::permission::close_bet_permission(ctx, request_id)
+    }
+
+    pub fn batch_close_bet_permissions<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchCloseBetPermissions<'info>>,
+        request_ids: Vec<String>,
+    ) -> Result<()> {
+        instructions::permission::batch_close_bet_permissions(ctx, request_ids)
+    }
+
     pub fn delegate_bet_permission<'info>(
         ctx: Context<DelegateBetPermission>,
         request_id: String,
@@ -71,7 +187,93 @@ pub mod swiv_privacy {
         instructions::delegation::delegate_bet_permission(ctx, request_id)
     }
 
+    pub fn create_pool_aggregates_permission(
+        ctx: Context<CreatePoolAggregatesPermission>,
+    ) -> Result<()> {
+        instructions::permission::create_pool_aggregates_permission(ctx)
+    }
+
+    pub fn delegate_pool_aggregates_permission(
+        ctx: Context<DelegatePoolAggregatesPermission>,
+    ) -> Result<()> {
+        instructions::delegation::delegate_pool_aggregates_permission(ctx)
+    }
+
+    pub fn delegate_pool_aggregates(ctx: Context<DelegatePoolAggregates>) -> Result<()> {
+        instructions::delegation::delegate_pool_aggregates(ctx)
+    }
+
+    pub fn undelegate_pool_aggregates(ctx: Context<UndelegatePoolAggregates>) -> Result<()> {
+        instructions::delegation::undelegate_pool_aggregates(ctx)
+    }
+
+    // --- SHIELDED DEPOSITS ---
+    pub fn initialize_deposit_pool(
+        ctx: Context<InitializeDepositPool>,
+        denomination: u64,
+    ) -> Result<()> {
+        instructions::shielded::initialize_deposit_pool(ctx, denomination)
+    }
+
+    pub fn deposit_shielded(ctx: Context<DepositShielded>, notes: u64) -> Result<()> {
+        instructions::shielded::deposit_shielded(ctx, notes)
+    }
+
+    pub fn withdraw_shielded(ctx: Context<WithdrawShielded>, notes: u64) -> Result<()> {
+        instructions::shielded::withdraw_shielded(ctx, notes)
+    }
+
+    pub fn create_shielded_balance_permission(
+        ctx: Context<CreateShieldedBalancePermission>,
+    ) -> Result<()> {
+        instructions::permission::create_shielded_balance_permission(ctx)
+    }
+
+    pub fn delegate_shielded_balance_permission(
+        ctx: Context<DelegateShieldedBalancePermission>,
+    ) -> Result<()> {
+        instructions::delegation::delegate_shielded_balance_permission(ctx)
+    }
+
+    pub fn delegate_shielded_balance(ctx: Context<DelegateShieldedBalance>) -> Result<()> {
+        instructions::delegation::delegate_shielded_balance(ctx)
+    }
+
+    pub fn undelegate_shielded_balance(ctx: Context<UndelegateShieldedBalance>) -> Result<()> {
+        instructions::delegation::undelegate_shielded_balance(ctx)
+    }
+
+    pub fn allocate_shielded_stake(
+        ctx: Context<AllocateShieldedStake>,
+        amount: u64,
+        request_id: String,
+    ) -> Result<()> {
+        instructions::shielded::allocate_shielded_stake(ctx, amount, request_id)
+    }
+
+    pub fn resize_shielded_stake(
+        ctx: Context<ResizeShieldedStake>,
+        request_id: String,
+        new_shielded_stake: u64,
+    ) -> Result<()> {
+        instructions::shielded::resize_shielded_stake(ctx, request_id, new_shielded_stake)
+    }
+
+    pub fn move_shielded_stake(
+        ctx: Context<MoveShieldedStake>,
+        amount: u64,
+        from_request_id: String,
+        to_request_id: String,
+    ) -> Result<()> {
+        instructions::shielded::move_shielded_stake(ctx, amount, from_request_id, to_request_id)
+    }
+
+    pub fn settle_shielded_stakes(ctx: Context<SettleShieldedStakes>) -> Result<()> {
+        instructions::shielded::settle_shielded_stakes(ctx)
+    }
+
     // --- POOL ---
+    #[allow(clippy::too_many_arguments)]
     pub fn create_pool(
         ctx: Context<CreatePool>,
         pool_id: u64,
@@ -80,6 +282,10 @@ pub mod swiv_privacy {
         end_time: i64,
         max_accuracy_buffer: u64,
         conviction_bonus_bps: u64,
+        fee_bps_override: Option<u64>,
+        fee_mode: FeeMode,
+        seed_amount: u64,
+        privacy: PrivacyPolicy,
     ) -> Result<()> {
         pool::create_pool(
             ctx,
@@ -89,27 +295,74 @@ pub mod swiv_privacy {
             end_time,
             max_accuracy_buffer,
             conviction_bonus_bps,
+            fee_bps_override,
+            fee_mode,
+            seed_amount,
+            privacy,
         )
     }
 
+    pub fn create_pool_aggregates(ctx: Context<CreatePoolAggregates>) -> Result<()> {
+        instructions::pool_aggregates::create_pool_aggregates(ctx)
+    }
+
+    pub fn sync_pool_aggregates(ctx: Context<SyncPoolAggregates>) -> Result<()> {
+        instructions::pool_aggregates::sync_pool_aggregates(ctx)
+    }
+
     // --- BET ---
     pub fn place_bet(ctx: Context<PlaceBet>, prediction: u64, request_id: String) -> Result<()> {
         pool::place_bet(ctx, prediction, request_id)
     }
 
-    pub fn init_bet(ctx: Context<InitBet>, amount: u64, request_id: String) -> Result<()> {
-        pool::init_bet(ctx, amount, request_id)
+    pub fn init_bet(
+        ctx: Context<InitBet>,
+        amount: u64,
+        request_id: String,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        pool::init_bet(ctx, amount, request_id, referrer)
     }
     pub fn resolve_pool(ctx: Context<ResolvePool>, final_outcome: u64) -> Result<()> {
         pool::resolve_pool(ctx, final_outcome)
     }
 
+    pub fn cancel_pool(ctx: Context<CancelPool>, creator_fault: bool) -> Result<()> {
+        pool::cancel_pool(ctx, creator_fault)
+    }
+
+    pub fn settle_creator_bond(ctx: Context<SettleCreatorBond>) -> Result<()> {
+        pool::settle_creator_bond(ctx)
+    }
+
+    pub fn reclaim_seed_subsidy(ctx: Context<ReclaimSeedSubsidy>) -> Result<()> {
+        pool::reclaim_seed_subsidy(ctx)
+    }
+
+    pub fn sponsor_pool(ctx: Context<SponsorPool>, amount: u64) -> Result<()> {
+        pool::sponsor_pool(ctx, amount)
+    }
+
+    pub fn claim_sponsor_reward(ctx: Context<ClaimSponsorReward>) -> Result<()> {
+        pool::claim_sponsor_reward(ctx)
+    }
+
+    pub fn refund_sponsor_reward(ctx: Context<RefundSponsorReward>) -> Result<()> {
+        pool::refund_sponsor_reward(ctx)
+    }
+
     pub fn batch_calculate_weights<'info>(
         ctx: Context<'_, '_, '_, 'info, BatchCalculateWeights<'info>>,
     ) -> Result<()> {
         admin::batch_calculate_weights(ctx)
     }
 
+    pub fn calculate_weights_in_rollup<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchCalculateWeights<'info>>,
+    ) -> Result<()> {
+        admin::calculate_weights_in_rollup(ctx)
+    }
+
     pub fn finalize_weights(ctx: Context<FinalizeWeights>) -> Result<()> {
         pool::finalize_weights(ctx)
     }
@@ -118,6 +371,16 @@ pub mod swiv_privacy {
         pool::claim_reward(ctx)
     }
 
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        pool::claim_referral_rewards(ctx)
+    }
+
+    pub fn credit_referral_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreditReferralFees<'info>>,
+    ) -> Result<()> {
+        pool::credit_referral_fees(ctx)
+    }
+
     pub fn update_bet(ctx: Context<UpdateBet>, new_prediction: u64) -> Result<()> {
         pool::update_bet(ctx, new_prediction)
     }
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Marks `validator` as an approved TEE validator that accounts may be
+/// delegated to.
+#[account]
+#[derive(InitSpace)]
+pub struct ApprovedValidator {
+    pub validator: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl ApprovedValidator {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
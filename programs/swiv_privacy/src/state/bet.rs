@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum BetStatus {
     Pending,
     Active,
@@ -9,6 +9,7 @@ pub enum BetStatus {
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct Bet {
     pub user_pubkey: Pubkey,
     pub pool_pubkey: Pubkey,
@@ -27,8 +28,21 @@ pub struct Bet {
     pub status: BetStatus,
     
     pub bump: u8,
+
+    pub referrer: Option<Pubkey>,
+    pub version: u8,
+
+    /// Part of `stake` funded from a shielded balance, which can be resized or
+    /// moved inside the rollup.
+    pub shielded_stake: u64,
+    /// Set once the referral share held back for this bet has been credited.
+    pub referral_credited: bool,
+    /// Compliance viewer added to this bet's permission when it was created,
+    /// kept so it stays protected after the protocol rotates its viewer.
+    pub compliance_viewer: Pubkey,
 }
 
 impl Bet {
-    pub const SPACE: usize = 250; 
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    pub const CURRENT_VERSION: u8 = 4;
 }
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::Role;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ConfigParam {
+    ProtocolFeeBps(u64),
+    Treasury(Pubkey),
+    Admin {
+        new_admin: Pubkey,
+        valid_for_seconds: Option<i64>,
+    },
+    MaxPauseDuration(i64),
+    ConfigTimelock(i64),
+    ComplianceViewer(Pubkey),
+    FeeSplit {
+        creator_fee_share_bps: u64,
+        referrer_fee_share_bps: u64,
+    },
+    Role {
+        role: Role,
+        member: Pubkey,
+    },
+    /// With `open_pool_creation` set, anyone (or only whitelisted creators) may
+    /// create pools by posting `creator_bond_amount` of the pool's stake mint.
+    PoolCreationPolicy {
+        open_pool_creation: bool,
+        creator_whitelist_enabled: bool,
+        creator_bond_amount: u64,
+    },
+}
+
+/// A protocol parameter change waiting out `Protocol::config_timelock`.
+#[account]
+#[derive(InitSpace)]
+pub struct ConfigChange {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: ConfigParam,
+    pub queued_at: i64,
+    pub effective_at: i64,
+    pub bump: u8,
+}
+
+impl ConfigChange {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
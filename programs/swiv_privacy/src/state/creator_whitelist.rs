@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Marks `creator` as allowed to open pools while the creator whitelist is on.
+#[account]
+#[derive(InitSpace)]
+pub struct WhitelistedCreator {
+    pub creator: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl WhitelistedCreator {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
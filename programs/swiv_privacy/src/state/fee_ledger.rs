@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-level fee accounting for a single stake mint. The tokens
+/// themselves sit in the matching `fee_vault` PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeLedger {
+    pub mint: Pubkey,
+    pub total_accrued: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl FeeLedger {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    pub fn available(&self) -> u64 {
+        self.total_accrued.saturating_sub(self.total_withdrawn)
+    }
+}
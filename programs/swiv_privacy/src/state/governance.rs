@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_GOVERNANCE_SIGNERS, PROPOSAL_EXPIRY_SECONDS};
+use crate::state::ConfigParam;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum GovernanceAction {
+    /// Completes a handover to the governance PDA itself, which cannot sign `accept_admin`.
+    AcceptAdmin,
+    CancelAdminTransfer,
+    SetPause {
+        paused: bool,
+    },
+    CancelPool {
+        pool: Pubkey,
+        creator_fault: bool,
+    },
+    ResolvePool {
+        pool: Pubkey,
+        final_outcome: u64,
+    },
+    QueueConfigChange {
+        change: ConfigParam,
+    },
+    UpdateSigners {
+        #[max_len(MAX_GOVERNANCE_SIGNERS)]
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+/// M-of-N signer set. Once `Protocol::admin` is this PDA, admin actions go
+/// through proposals.
+#[account]
+#[derive(InitSpace)]
+pub struct Governance {
+    #[max_len(MAX_GOVERNANCE_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl Governance {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+    #[max_len(MAX_GOVERNANCE_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.created_at.saturating_add(PROPOSAL_EXPIRY_SECONDS)
+    }
+}
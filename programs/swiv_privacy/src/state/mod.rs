@@ -1,7 +1,25 @@
 pub mod protocol;
 pub mod pool;
 pub mod bet;
+pub mod fee_ledger;
+pub mod config_change;
+pub mod governance;
+pub mod creator_whitelist;
+pub mod sponsor_reward;
+pub mod referrer;
+pub mod shielded;
+pub mod pool_aggregates;
+pub mod approved_validator;
 
 pub use protocol::*;
 pub use pool::*;
-pub use bet::*;
\ No newline at end of file
+pub use bet::*;
+pub use fee_ledger::*;
+pub use config_change::*;
+pub use governance::*;
+pub use creator_whitelist::*;
+pub use sponsor_reward::*;
+pub use referrer::*;
+pub use shielded::*;
+pub use pool_aggregates::*;
+pub use approved_validator::*;
\ No newline at end of file
@@ -1,8 +1,26 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use crate::constants::MAX_POOL_TITLE_LEN;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum FeeMode {
+    OnVolume,
+    OnProfit,
+}
+
+/// What a pool's instructions may log or emit before it is resolved. In
+/// strict mode predictions never appear, and neither do stakes with `hide_stakes`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct PrivacyPolicy {
+    pub strict: bool,
+    pub hide_stakes: bool,
+}
 
 #[account]
+#[derive(InitSpace)]
 pub struct Pool {
     pub created_by: Pubkey,
+    #[max_len(MAX_POOL_TITLE_LEN)]
     pub title: String,
     pub pool_id: u64,
     pub stake_token_mint: Pubkey,
@@ -23,4 +41,71 @@ pub struct Pool {
     pub total_participants: u64,
     
     pub bump: u8,
-}
\ No newline at end of file
+
+    pub referred_volume: u64,
+    pub referral_fees: u64,
+    pub fee_bps: u64,
+    pub fee_mode: FeeMode,
+    pub frozen: bool,
+    pub is_cancelled: bool,
+    pub version: u8,
+    pub creator_bond: u64,
+    pub bond_settled: bool,
+    pub cancelled_for_creator_fault: bool,
+    pub seed_subsidy: u64,
+    pub subsidy_reclaimed: bool,
+    pub privacy: PrivacyPolicy,
+    pub shielded_volume: u64,
+    pub shielded_settled: bool,
+    pub private_aggregates: bool,
+    pub aggregates_synced: bool,
+    /// Approved validator the pool and its bets must be delegated to; unset when default.
+    pub pinned_validator: Pubkey,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    pub const CURRENT_VERSION: u8 = 7;
+
+    /// Pools staked in wrapped SOL accept and pay out native lamports directly.
+    pub fn is_native(&self) -> bool {
+        self.stake_token_mint == native_mint::ID
+    }
+
+    pub fn reveals_predictions(&self) -> bool {
+        !self.privacy.strict || self.is_resolved
+    }
+
+    /// Stakes allocated from shielded balances still sit in the deposit vault
+    /// until `settle_shielded_stakes` moves them into the pool vault.
+    pub fn shielded_stakes_settled(&self) -> bool {
+        self.shielded_volume == 0 || self.shielded_settled
+    }
+
+    /// Pools with private aggregates only have complete totals on L1 once
+    /// `sync_pool_aggregates` has run.
+    pub fn aggregates_settled(&self) -> bool {
+        !self.private_aggregates || self.aggregates_synced
+    }
+
+    pub fn accepts_validator(&self, validator: &Pubkey) -> bool {
+        self.pinned_validator == Pubkey::default() || self.pinned_validator == *validator
+    }
+
+    /// A referred bet's part of the volume-fee referral share held back in
+    /// `finalize_weights`, in proportion to its stake.
+    pub fn held_back_referral_share(&self, stake: u64) -> u64 {
+        if self.referred_volume == 0 {
+            return 0;
+        }
+        (self.referral_fees as u128)
+            .checked_mul(stake as u128)
+            .unwrap()
+            .checked_div(self.referred_volume as u128)
+            .unwrap() as u64
+    }
+
+    pub fn reveals_stakes(&self) -> bool {
+        !(self.privacy.strict && self.privacy.hide_stakes) || self.is_resolved
+    }
+}
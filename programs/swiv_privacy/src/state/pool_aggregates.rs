@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Running totals for a pool, kept inside the rollup behind a permission
+/// while betting is open. L1 only learns them once they are synced into the
+/// pool after `end_time`. Volumes here cover shielded stake only; stake paid
+/// through `init_bet` is counted on the pool directly.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolAggregates {
+    pub pool: Pubkey,
+    pub total_volume: u64,
+    pub shielded_volume: u64,
+    pub referred_volume: u64,
+    pub total_participants: u64,
+    pub synced: bool,
+    pub bump: u8,
+}
+
+impl PoolAggregates {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
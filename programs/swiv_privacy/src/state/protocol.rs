@@ -1,6 +1,17 @@
 use anchor_lang::prelude::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    PoolCreator,
+    Resolver,
+    Pauser,
+    FeeManager,
+    CrankOperator,
+    ValidatorManager,
+}
+
 #[account]
+#[derive(InitSpace)]
 pub struct Protocol {
     pub admin: Pubkey,
     pub treasury_wallet: Pubkey,
@@ -8,8 +19,76 @@ pub struct Protocol {
     pub paused: bool,
     pub batch_settle_wait_duration: i64,
     pub total_pools: u64,
+    pub creator_fee_share_bps: u64,
+    pub referrer_fee_share_bps: u64,
+    pub pending_admin: Option<Pubkey>,
+    pub pending_admin_expiry: i64,
+    pub pool_creator: Pubkey,
+    pub resolver: Pubkey,
+    pub pauser: Pubkey,
+    pub fee_manager: Pubkey,
+    pub crank_operator: Pubkey,
+    pub pause_flags: u8,
+    pub paused_at: i64,
+    pub max_pause_duration: i64,
+    pub config_timelock: i64,
+    pub config_change_count: u64,
+    pub version: u8,
+    pub open_pool_creation: bool,
+    pub creator_whitelist_enabled: bool,
+    pub creator_bond_amount: u64,
+    /// Read-only member added to every new bet permission; unset when default.
+    pub compliance_viewer: Pubkey,
+    pub validator_manager: Pubkey,
 }
 
 impl Protocol {
-    pub const BASE_LEN: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8;
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    pub const CURRENT_VERSION: u8 = 4;
+
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::PoolCreator => self.pool_creator,
+            Role::Resolver => self.resolver,
+            Role::Pauser => self.pauser,
+            Role::FeeManager => self.fee_manager,
+            Role::CrankOperator => self.crank_operator,
+            Role::ValidatorManager => self.validator_manager,
+        }
+    }
+
+    pub fn set_role_holder(&mut self, role: Role, member: Pubkey) {
+        match role {
+            Role::PoolCreator => self.pool_creator = member,
+            Role::Resolver => self.resolver = member,
+            Role::Pauser => self.pauser = member,
+            Role::FeeManager => self.fee_manager = member,
+            Role::CrankOperator => self.crank_operator = member,
+            Role::ValidatorManager => self.validator_manager = member,
+        }
+    }
+
+    /// `paused` stops everything; `pause_flags` stops individual stages.
+    /// Either lapses once `max_pause_duration` has passed since `paused_at`.
+    pub fn is_paused(&self, flag: u8) -> bool {
+        if !self.paused && self.pause_flags & flag == 0 {
+            return false;
+        }
+        if self.max_pause_duration == 0 {
+            return true;
+        }
+        match Clock::get() {
+            Ok(clock) => clock.unix_timestamp <= self.paused_at.saturating_add(self.max_pause_duration),
+            Err(_) => true,
+        }
+    }
+
+    /// The admin implicitly holds every role; an unassigned role is `Pubkey::default()`.
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        if *key == self.admin {
+            return true;
+        }
+        let holder = self.role_holder(role);
+        holder != Pubkey::default() && holder == *key
+    }
 }
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Per-referrer, per-mint referral stats and claimable rebates. Credited
+/// rebates are held in the mint's `fee_vault` until claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerAccount {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub referred_bets: u64,
+    pub referred_volume: u64,
+    pub total_earned: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl ReferrerAccount {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    pub fn claimable(&self) -> u64 {
+        self.total_earned.saturating_sub(self.total_claimed)
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-wide deposit pool for one stake mint. Deposits and withdrawals
+/// move whole multiples of `denomination`, so L1 only shows note counts.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositPool {
+    pub mint: Pubkey,
+    pub denomination: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_settled: u64,
+    pub bump: u8,
+}
+
+impl DepositPool {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
+/// A user's unallocated shielded funds. Delegated to the rollup, where it is
+/// allocated to bets without any L1 transfer. The bets themselves must still
+/// be opened and delegated on L1.
+#[account]
+#[derive(InitSpace)]
+pub struct ShieldedBalance {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+impl ShieldedBalance {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+/// Extra rewards in `mint` put up by one sponsor for one pool. The tokens sit in
+/// the matching `sponsor_vault` PDA and are shared by bet weight at claim time.
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorReward {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub sponsor: Pubkey,
+    pub total_amount: u64,
+    pub total_claimed: u64,
+    pub refunded: bool,
+    pub bump: u8,
+}
+
+impl SponsorReward {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
+/// Marks that a bet has taken its share of one sponsor reward.
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorClaim {
+    pub sponsor_reward: Pubkey,
+    pub bet: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl SponsorClaim {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
@@ -1,8 +1,19 @@
+use crate::constants::BPS_DENOMINATOR;
 use crate::errors::CustomError;
 use anchor_lang::prelude::*;
 
 pub const MATH_PRECISION: u128 = 1_000_000; 
 
+pub fn calculate_fee(amount: u64, fee_bps: u64) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(fee as u64)
+}
+
 pub fn calculate_accuracy_score(
     prediction: u64,
     result: u64,
@@ -1,3 +1,5 @@
 pub mod math;
+pub mod signature;
 
-pub use math::*;
\ No newline at end of file
+pub use math::*;
+pub use signature::*;
\ No newline at end of file
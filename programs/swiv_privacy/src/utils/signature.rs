@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+#[allow(deprecated)]
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::token::TokenAccount;
+use crate::errors::CustomError;
+use crate::state::Pool;
+
+const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+const CLAIM_MESSAGE_PREFIX: &[u8] = b"swiv:claim";
+
+// Offsets into the ed25519 program's instruction data for a single signature.
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+
+/// The message a bet owner signs to release a bet's payout to `destination`.
+pub fn claim_message(bet: &Pubkey, destination: &Pubkey) -> Vec<u8> {
+    [CLAIM_MESSAGE_PREFIX, bet.as_ref(), destination.as_ref()].concat()
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<usize> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(CustomError::InvalidSignatureInstruction)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+}
+
+/// Checks that the instruction right before this one is an ed25519 program
+/// verification of `message` by `signer`, with all data inline.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current_index > 0, CustomError::InvalidSignatureInstruction);
+
+    let ix = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    require!(
+        ix.program_id == ED25519_PROGRAM_ID,
+        CustomError::InvalidSignatureInstruction
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        CustomError::InvalidSignatureInstruction
+    );
+
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_ix_index = read_u16(data, offsets + 2)?;
+    let pubkey_offset = read_u16(data, offsets + 4)?;
+    let pubkey_ix_index = read_u16(data, offsets + 6)?;
+    let message_offset = read_u16(data, offsets + 8)?;
+    let message_size = read_u16(data, offsets + 10)?;
+    let message_ix_index = read_u16(data, offsets + 12)?;
+
+    // Every part must live in the ed25519 instruction itself.
+    let inline = u16::MAX as usize;
+    require!(
+        signature_ix_index == inline && pubkey_ix_index == inline && message_ix_index == inline,
+        CustomError::InvalidSignatureInstruction
+    );
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(CustomError::InvalidSignatureInstruction)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(CustomError::InvalidSignatureInstruction)?;
+
+    require!(
+        signed_pubkey == signer.as_ref() && signed_message == message,
+        CustomError::InvalidSignatureInstruction
+    );
+
+    Ok(())
+}
+
+/// The account a payout is authorized for: the token account for SPL pools,
+/// or for native SOL pools the wallet the unwrapped lamports are closed into.
+pub fn payout_destination(
+    pool: &Pool,
+    user: &Signer,
+    user_token_account: &Account<TokenAccount>,
+    recipient: &Option<UncheckedAccount>,
+) -> Pubkey {
+    if pool.is_native() {
+        recipient.as_ref().map_or(user.key(), |recipient| recipient.key())
+    } else {
+        user_token_account.key()
+    }
+}
+
+/// A bet's payout may go to `destination` if the bet owner signs the
+/// transaction, or if it carries the owner's ed25519 signature over
+/// `claim_message(bet, destination)`.
+pub fn authorize_bet_payout(
+    bet_owner: &Pubkey,
+    signer: &Pubkey,
+    bet: &Pubkey,
+    destination: &Pubkey,
+    instructions_sysvar: Option<&AccountInfo>,
+) -> Result<()> {
+    if bet_owner == signer {
+        return Ok(());
+    }
+
+    let instructions_sysvar = instructions_sysvar.ok_or(CustomError::Unauthorized)?;
+    verify_ed25519_signature(
+        instructions_sysvar,
+        bet_owner,
+        &claim_message(bet, destination),
+    )
+}
@@ -1,8 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { SwivPrivacy } from "../target/types/swiv_privacy";
-import { SEED_PROTOCOL } from "./utils";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { SEED_CONFIG_CHANGE, SEED_PROTOCOL } from "./utils";
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { createMint } from "@solana/spl-token";
 
 describe("1. Setup & Admin", () => {
@@ -30,15 +31,188 @@ describe("1. Setup & Admin", () => {
         .rpc();
       console.log("    ✅ Protocol Initialized");
     } else {
+      console.log("    ✅ Protocol Already Initialized");
+    }
+  });
+
+  async function nextConfigChangePda(configPda: PublicKey): Promise<PublicKey> {
+    const protocol = await program.account.protocol.fetch(configPda);
+    const [configChangePda] = PublicKey.findProgramAddressSync(
+      [SEED_CONFIG_CHANGE, protocol.configChangeCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    return configChangePda;
+  }
+
+  it("Rejects a config timelock below the minimum", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .queueConfigChange({ configTimelock: { 0: new anchor.BN(60) } })
+        .accountsPartial({
+          admin: admin.publicKey,
+          protocol: configPda,
+          configChange: await nextConfigChangePda(configPda),
+        })
+        .rpc();
+      expect.fail("A timelock below MIN_CONFIG_TIMELOCK was accepted");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidDuration");
+    }
+    console.log("    ✅ Short Timelock Rejected");
+  });
+
+  it("Queued fee split cannot execute before the timelock", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+    const configChangePda = await nextConfigChangePda(configPda);
+
+    await program.methods
+      .queueConfigChange({
+        feeSplit: {
+          creatorFeeShareBps: new anchor.BN(1_000),
+          referrerFeeShareBps: new anchor.BN(500),
+        },
+      })
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: configPda,
+        configChange: configChangePda,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .executeConfigChange()
+        .accountsPartial({
+          executor: admin.publicKey,
+          protocol: configPda,
+          configChange: configChangePda,
+          proposer: admin.publicKey,
+        })
+        .rpc();
+      expect.fail("Fee split applied before the timelock elapsed");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("TimelockNotElapsed");
+    }
+
+    await program.methods
+      .cancelConfigChange()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: configPda,
+        configChange: configChangePda,
+        proposer: admin.publicKey,
+      })
+      .rpc();
+    console.log("    ✅ Fee Split Held By Timelock");
+  });
+
+  it("Rejects a fee split above the maximum share", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+
+    try {
       await program.methods
-        .updateConfig(null, new anchor.BN(300))
+        .queueConfigChange({
+          feeSplit: {
+            creatorFeeShareBps: new anchor.BN(4_000),
+            referrerFeeShareBps: new anchor.BN(2_000),
+          },
+        })
+        .accountsPartial({
+          admin: admin.publicKey,
+          protocol: configPda,
+          configChange: await nextConfigChangePda(configPda),
+        })
+        .rpc();
+      expect.fail("A fee split above MAX_FEE_SHARE_BPS was accepted");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("FeeShareTooHigh");
+    }
+    console.log("    ✅ Oversized Fee Split Rejected");
+  });
+
+  it("Only the pending admin can accept an admin transfer", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+    const outsider = Keypair.generate();
+
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accountsPartial({ newAdmin: outsider.publicKey, protocol: configPda })
+        .signers([outsider])
+        .rpc();
+      expect.fail("An outsider accepted admin authority");
+    } catch (e) {
+      expect(["NoPendingAdmin", "Unauthorized"]).to.include(e.error?.errorCode?.code);
+    }
+    console.log("    ✅ Unsolicited Admin Acceptance Rejected");
+  });
+
+  it("Rejects unknown pause flags", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .setPauseFlags(1 << 7)
+        .accountsPartial({ admin: admin.publicKey, protocol: configPda })
+        .rpc();
+      expect.fail("An unknown pause flag was accepted");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidPauseFlags");
+    }
+    console.log("    ✅ Unknown Pause Flags Rejected");
+  });
+
+  it("Rejects migrating a protocol that is already current", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .migrateProtocol()
         .accountsPartial({
           admin: admin.publicKey,
           protocol: configPda,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
-      console.log("    ✅ Protocol Config Updated");
+      expect.fail("A current protocol was migrated again");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("AlreadyMigrated");
+    }
+    console.log("    ✅ Current Protocol Not Re-Migrated");
+  });
+
+  it("Only the validator manager role can approve validators", async () => {
+    const outsider = Keypair.generate();
+    try {
+      await program.methods
+        .addApprovedValidator(Keypair.generate().publicKey)
+        .accountsPartial({ admin: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+      expect.fail("An outsider approved a validator");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("Unauthorized");
     }
+    console.log("    ✅ Validator Allow-List Is Role-Gated");
   });
 });
\ No newline at end of file
@@ -19,6 +19,8 @@ import * as fs from "fs";
 import * as path from "path";
 import {
   SEED_BET,
+  SEED_FEE_LEDGER,
+  SEED_FEE_VAULT,
   SEED_POOL,
   SEED_PROTOCOL,
   PERMISSION_PROGRAM_ID,
@@ -176,6 +178,16 @@ describe("Production Flow", () => {
         .rpc();
     } catch (e) { }
 
+    try {
+      await program.methods
+        .addApprovedValidator(TEE_VALIDATOR)
+        .accountsPartial({
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (e) { }
+
     const protocol = await program.account.protocol.fetch(protocolPda);
     poolId = protocol.totalPools.toNumber();
   });
@@ -213,6 +225,10 @@ describe("Production Flow", () => {
         END_TIME,
         new anchor.BN(10),
         new anchor.BN(3),
+        null,
+        { onVolume: {} },
+        new anchor.BN(0),
+        { strict: false, hideStakes: false },
       )
       .accountsPartial({
         protocol: protocolPda,
@@ -221,6 +237,8 @@ describe("Production Flow", () => {
         tokenMint: usdcMint,
         createdBy: admin.publicKey,
         createdByTokenAccount: adminAta.address,
+        creatorWhitelist: null,
+        bondVault: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -259,14 +277,16 @@ describe("Production Flow", () => {
 
       const tx = new anchor.web3.Transaction().add(
         await program.methods
-          .initBet(betAmount, requestId)
+          .initBet(betAmount, requestId, null)
           .accountsPartial({
             user: user.publicKey,
+            betOwner: null,
             protocol: protocolPda,
             pool: poolPda,
             poolVault: vaultPda,
             userTokenAccount: userAtas[i],
             bet: betPda,
+            referrerAccount: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -363,6 +383,7 @@ describe("Production Flow", () => {
           user: user.publicKey,
           pool: poolPda,
           bet: betPda,
+          poolAggregates: null,
         })
         .instruction();
 
@@ -459,9 +480,9 @@ describe("Production Flow", () => {
     // Retry delegation if L1 is busy
     await withRetry(async () => {
       const tx = await program.methods
-        .delegatePool(new anchor.BN(poolId))
+        .delegatePool()
         .accountsPartial({
-          admin: admin.publicKey,
+          authority: admin.publicKey,
           protocol: protocolPda,
           pool: poolPda,
           validator: TEE_VALIDATOR,
@@ -532,7 +553,11 @@ describe("Production Flow", () => {
     await withRetry(async () => {
       const calcTx = await erProgram.methods
         .batchCalculateWeights()
-        .accountsPartial({ admin: admin.publicKey, pool: poolPda })
+        .accountsPartial({
+          admin: admin.publicKey,
+          protocol: protocolPda,
+          pool: poolPda,
+        })
         .remainingAccounts(batchAccounts)
         .rpc();
       console.log(`    ✅ Weights Calculated (Sig: ${calcTx})`);
@@ -564,8 +589,8 @@ describe("Production Flow", () => {
     await withRetry(async () => {
       const finalUndelegateTx = await erProgram.methods
         .undelegatePool()
-        .accounts({
-          admin: admin.publicKey,
+        .accountsPartial({
+          authority: admin.publicKey,
           protocol: protocolPda,
           pool: poolPda,
         })
@@ -621,9 +646,16 @@ describe("Production Flow", () => {
 
     // --- 2. FINALIZE WEIGHTS (The Missing Step) ---
     // This calculates fees and unlocks the vault for claimers
+    const [feeLedgerPda] = PublicKey.findProgramAddressSync(
+      [SEED_FEE_LEDGER, usdcMint.toBuffer()],
+      program.programId,
+    );
+    const [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [SEED_FEE_VAULT, usdcMint.toBuffer()],
+      program.programId,
+    );
+
     try {
-      // In Step 1, we set treasuryWallet = admin.publicKey.
-      // So we pass the admin's ATA as the treasury token account.
       const adminAta = await getOrCreateAssociatedTokenAccount(
         provider.connection,
         admin,
@@ -638,8 +670,12 @@ describe("Production Flow", () => {
           protocol: protocolPda,
           pool: poolPda,
           poolVault: vaultPda,
-          treasuryTokenAccount: adminAta.address,
+          tokenMint: usdcMint,
+          feeLedger: feeLedgerPda,
+          feeVault: feeVaultPda,
+          creatorTokenAccount: adminAta.address,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([admin])
         .rpc();
@@ -670,10 +706,17 @@ describe("Production Flow", () => {
           .claimReward()
           .accountsPartial({
             user: user.publicKey,
+            protocol: protocolPda,
             pool: poolPda,
             poolVault: vaultPda,
             bet: userBetPda,
             userTokenAccount: userAta,
+            recipient: null,
+            referrerAccount: null,
+            feeLedger: feeLedgerPda,
+            feeVault: feeVaultPda,
+            creatorTokenAccount: null,
+            instructionsSysvar: null,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([user])
@@ -0,0 +1,929 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import {
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  PERMISSION_PROGRAM_ID,
+  permissionPdaFromAccount,
+  SEED_APPROVED_VALIDATOR,
+  SEED_BET,
+  SEED_DEPOSIT_POOL,
+  SEED_DEPOSIT_VAULT,
+  SEED_FEE_LEDGER,
+  SEED_FEE_VAULT,
+  SEED_POOL,
+  SEED_POOL_AGGREGATES,
+  SEED_POOL_VAULT,
+  SEED_PROTOCOL,
+  SEED_SHIELDED_BALANCE,
+  sleep,
+  TEE_VALIDATOR,
+} from "./utils";
+
+const DENOMINATION = new anchor.BN(1e6);
+const BET_AMOUNT = new anchor.BN(10 * 1e6);
+
+const OPEN_POLICY = { strict: false, hideStakes: false };
+
+const AUTHORITY_FLAG = 1 << 0;
+const TX_LOGS_FLAG = 1 << 1;
+const TX_BALANCES_FLAG = 1 << 2;
+
+describe("3. Privacy Features", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const bettor = Keypair.generate();
+  const oneTimeOwner = Keypair.generate();
+  const viewer = Keypair.generate().publicKey;
+
+  let mint: PublicKey;
+  let bettorAta: PublicKey;
+  let adminAta: PublicKey;
+  let protocolPda: PublicKey;
+  let depositPoolPda: PublicKey;
+  let depositVaultPda: PublicKey;
+  let shieldedBalancePda: PublicKey;
+
+  let livePool: PublicKey;
+  let liveVault: PublicKey;
+  let liveEndTime: number;
+  const liveBets: PublicKey[] = [];
+  let oneTimeBet: PublicKey;
+
+  const viewerRequestId = "viewers";
+  let viewerBet: PublicKey;
+  let viewerPermission: PublicKey;
+
+  let futurePool: PublicKey;
+
+  function poolPdas(poolId: number): [PublicKey, PublicKey] {
+    const [pool] = PublicKey.findProgramAddressSync(
+      [SEED_POOL, admin.publicKey.toBuffer(), new anchor.BN(poolId).toBuffer("le", 8)],
+      program.programId
+    );
+    const [vault] = PublicKey.findProgramAddressSync(
+      [SEED_POOL_VAULT, pool.toBuffer()],
+      program.programId
+    );
+    return [pool, vault];
+  }
+
+  function betPda(pool: PublicKey, owner: PublicKey, requestId: string): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [SEED_BET, pool.toBuffer(), owner.toBuffer(), Buffer.from(requestId)],
+      program.programId
+    )[0];
+  }
+
+  async function createPool(
+    startTime: number,
+    endTime: number,
+    privacy = OPEN_POLICY
+  ): Promise<[PublicKey, PublicKey]> {
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    const poolId = protocol.totalPools.toNumber();
+    const [pool, vault] = poolPdas(poolId);
+
+    await program.methods
+      .createPool(
+        new anchor.BN(poolId),
+        `Privacy-${poolId}`,
+        new anchor.BN(startTime),
+        new anchor.BN(endTime),
+        new anchor.BN(10),
+        new anchor.BN(3),
+        null,
+        { onVolume: {} },
+        new anchor.BN(0),
+        privacy
+      )
+      .accountsPartial({
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        tokenMint: mint,
+        createdBy: admin.publicKey,
+        createdByTokenAccount: adminAta,
+        creatorWhitelist: null,
+        bondVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    return [pool, vault];
+  }
+
+  function feeAccounts() {
+    const [feeLedger] = PublicKey.findProgramAddressSync(
+      [SEED_FEE_LEDGER, mint.toBuffer()],
+      program.programId
+    );
+    const [feeVault] = PublicKey.findProgramAddressSync(
+      [SEED_FEE_VAULT, mint.toBuffer()],
+      program.programId
+    );
+    return { feeLedger, feeVault };
+  }
+
+  function claimAccounts(user: PublicKey, bet: PublicKey) {
+    return {
+      user,
+      protocol: protocolPda,
+      pool: livePool,
+      poolVault: liveVault,
+      bet,
+      userTokenAccount: bettorAta,
+      recipient: null,
+      referrerAccount: null,
+      ...feeAccounts(),
+      creatorTokenAccount: null,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  function claimMessage(bet: PublicKey, destination: PublicKey): Buffer {
+    return Buffer.concat([Buffer.from("swiv:claim"), bet.toBuffer(), destination.toBuffer()]);
+  }
+
+  async function transactionLogs(signature: string): Promise<string[]> {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx.meta.logMessages;
+  }
+
+  before(async () => {
+    [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: bettor.publicKey,
+          lamports: 0.1 * LAMPORTS_PER_SOL,
+        })
+      )
+    );
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    bettorAta = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, bettor.publicKey)
+    ).address;
+    adminAta = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, admin.publicKey)
+    ).address;
+    await mintTo(provider.connection, admin, mint, bettorAta, admin, 100 * 1e6);
+
+    try {
+      await program.methods
+        .addApprovedValidator(TEE_VALIDATOR)
+        .accountsPartial({ admin: admin.publicKey, systemProgram: SystemProgram.programId })
+        .rpc();
+    } catch (e) { }
+
+    [depositPoolPda] = PublicKey.findProgramAddressSync(
+      [SEED_DEPOSIT_POOL, mint.toBuffer()],
+      program.programId
+    );
+    [depositVaultPda] = PublicKey.findProgramAddressSync(
+      [SEED_DEPOSIT_VAULT, mint.toBuffer()],
+      program.programId
+    );
+    [shieldedBalancePda] = PublicKey.findProgramAddressSync(
+      [SEED_SHIELDED_BALANCE, mint.toBuffer(), bettor.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("Shielded deposit and withdrawal round-trip", async () => {
+    await program.methods
+      .initializeDepositPool(DENOMINATION)
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        tokenMint: mint,
+        depositPool: depositPoolPda,
+        depositVault: depositVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .depositShielded(new anchor.BN(3))
+      .accountsPartial({
+        owner: bettor.publicKey,
+        depositPool: depositPoolPda,
+        depositVault: depositVaultPda,
+        shieldedBalance: shieldedBalancePda,
+        ownerTokenAccount: bettorAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+
+    let balance = await program.account.shieldedBalance.fetch(shieldedBalancePda);
+    expect(balance.balance.toString()).to.equal(DENOMINATION.muln(3).toString());
+
+    const before = await provider.connection.getTokenAccountBalance(bettorAta);
+    await program.methods
+      .withdrawShielded(new anchor.BN(2))
+      .accountsPartial({
+        owner: bettor.publicKey,
+        depositPool: depositPoolPda,
+        depositVault: depositVaultPda,
+        shieldedBalance: shieldedBalancePda,
+        recipientTokenAccount: bettorAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([bettor])
+      .rpc();
+    const after = await provider.connection.getTokenAccountBalance(bettorAta);
+
+    balance = await program.account.shieldedBalance.fetch(shieldedBalancePda);
+    expect(balance.balance.toString()).to.equal(DENOMINATION.toString());
+    expect(new anchor.BN(after.value.amount).sub(new anchor.BN(before.value.amount)).toString())
+      .to.equal(DENOMINATION.muln(2).toString());
+    console.log("    ✅ Shielded Notes Deposited And Withdrawn");
+  });
+
+  it("Rejects withdrawing more notes than the shielded balance holds", async () => {
+    try {
+      await program.methods
+        .withdrawShielded(new anchor.BN(2))
+        .accountsPartial({
+          owner: bettor.publicKey,
+          depositPool: depositPoolPda,
+          depositVault: depositVaultPda,
+          shieldedBalance: shieldedBalancePda,
+          recipientTokenAccount: bettorAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bettor])
+        .rpc();
+      expect.fail("Withdrew more notes than were deposited");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InsufficientShieldedBalance");
+    }
+    console.log("    ✅ Overdrawn Withdrawal Rejected");
+  });
+
+  it("Moves whole notes between the shielded balance and a bet stake", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const startTime = now + 15;
+    const [pool, vault] = await createPool(startTime, now + 3600);
+    const [poolAggregates] = PublicKey.findProgramAddressSync(
+      [SEED_POOL_AGGREGATES, pool.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .createPoolAggregates()
+      .accountsPartial({
+        authority: admin.publicKey,
+        protocol: protocolPda,
+        pool,
+        poolAggregates,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .depositShielded(new anchor.BN(2))
+      .accountsPartial({
+        owner: bettor.publicKey,
+        depositPool: depositPoolPda,
+        depositVault: depositVaultPda,
+        shieldedBalance: shieldedBalancePda,
+        ownerTokenAccount: bettorAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+
+    await sleep(Math.max(0, startTime * 1000 - Date.now() + 2000));
+    const requestId = "shielded";
+    const bet = betPda(pool, bettor.publicKey, requestId);
+    await program.methods
+      .initBet(new anchor.BN(0), requestId, null)
+      .accountsPartial({
+        user: bettor.publicKey,
+        betOwner: null,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        userTokenAccount: bettorAta,
+        bet,
+        referrerAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+
+    const stakeAccounts = {
+      owner: bettor.publicKey,
+      betOwner: bettor.publicKey,
+      protocol: protocolPda,
+      depositPool: depositPoolPda,
+      shieldedBalance: shieldedBalancePda,
+      pool,
+      poolAggregates,
+      bet,
+    };
+
+    try {
+      await program.methods
+        .allocateShieldedStake(DENOMINATION.muln(3).divn(2), requestId)
+        .accountsPartial(stakeAccounts)
+        .signers([bettor])
+        .rpc();
+      expect.fail("Allocated part of a note");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("PartialNote");
+    }
+
+    await program.methods
+      .allocateShieldedStake(DENOMINATION.muln(3), requestId)
+      .accountsPartial(stakeAccounts)
+      .signers([bettor])
+      .rpc();
+    expect((await program.account.bet.fetch(bet)).shieldedStake.toString())
+      .to.equal(DENOMINATION.muln(3).toString());
+    expect((await program.account.shieldedBalance.fetch(shieldedBalancePda)).balance.isZero())
+      .to.be.true;
+
+    await program.methods
+      .resizeShieldedStake(requestId, DENOMINATION)
+      .accountsPartial(stakeAccounts)
+      .signers([bettor])
+      .rpc();
+    expect((await program.account.bet.fetch(bet)).shieldedStake.toString())
+      .to.equal(DENOMINATION.toString());
+    expect((await program.account.shieldedBalance.fetch(shieldedBalancePda)).balance.toString())
+      .to.equal(DENOMINATION.muln(2).toString());
+    console.log("    ✅ Shielded Stake Allocated And Resized");
+  });
+
+  it("Keeps pool aggregates private until betting closes", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const aggregatesPda = (pool: PublicKey) =>
+      PublicKey.findProgramAddressSync([SEED_POOL_AGGREGATES, pool.toBuffer()], program.programId)[0];
+
+    const [futurePool] = await createPool(now + 3600, now + 7200);
+    await program.methods
+      .createPoolAggregates()
+      .accountsPartial({
+        authority: admin.publicKey,
+        protocol: protocolPda,
+        pool: futurePool,
+        poolAggregates: aggregatesPda(futurePool),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    expect((await program.account.pool.fetch(futurePool)).privateAggregates).to.be.true;
+
+    try {
+      await program.methods
+        .syncPoolAggregates()
+        .accountsPartial({
+          payer: admin.publicKey,
+          pool: futurePool,
+          poolAggregates: aggregatesPda(futurePool),
+        })
+        .rpc();
+      expect.fail("Aggregates were synced while betting was still open");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("SettlementTooEarly");
+    }
+
+    const [openPool] = await createPool(now, now + 3600);
+    try {
+      await program.methods
+        .createPoolAggregates()
+        .accountsPartial({
+          authority: admin.publicKey,
+          protocol: protocolPda,
+          pool: openPool,
+          poolAggregates: aggregatesPda(openPool),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Aggregates were split off a pool already open for betting");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("DurationTooShort");
+    }
+    console.log("    ✅ Pool Aggregates Kept Private");
+  });
+
+  it("Keeps predictions and stakes out of logs and events in strict pools", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const [pool, vault] = await createPool(now, now + 3600, { strict: true, hideStakes: true });
+    const requestId = "strict";
+    const bet = betPda(pool, bettor.publicKey, requestId);
+
+    await program.methods
+      .initBet(BET_AMOUNT, requestId, null)
+      .accountsPartial({
+        user: bettor.publicKey,
+        betOwner: null,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        userTokenAccount: bettorAta,
+        bet,
+        referrerAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+
+    const placed = await program.methods
+      .placeBet(new anchor.BN(424242), requestId)
+      .accountsPartial({ user: bettor.publicKey, pool, bet, poolAggregates: null })
+      .signers([bettor])
+      .rpc();
+    const updated = await program.methods
+      .updateBet(new anchor.BN(434343))
+      .accountsPartial({ user: bettor.publicKey, protocol: protocolPda, bet, pool })
+      .signers([bettor])
+      .rpc();
+
+    const placedLogs = await transactionLogs(placed);
+    const updatedLogs = await transactionLogs(updated);
+    expect(placedLogs.join("\n")).to.not.include("424242");
+    expect(updatedLogs.join("\n")).to.not.include("434343");
+
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = Array.from(parser.parseLogs(placedLogs));
+    const betPlaced = events.find((event) => event.name === "BetPlaced");
+    expect(betPlaced).to.not.be.undefined;
+    expect(betPlaced.data.amount).to.be.null;
+    console.log("    ✅ Strict Pool Kept Bet Private");
+  });
+
+  it("Opens a bet under a co-signing one-time key", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    liveEndTime = now + 60;
+    [livePool, liveVault] = await createPool(now, liveEndTime);
+
+    const requestId = "one_time";
+    oneTimeBet = betPda(livePool, oneTimeOwner.publicKey, requestId);
+
+    await program.methods
+      .initBet(BET_AMOUNT, requestId, null)
+      .accountsPartial({
+        user: bettor.publicKey,
+        betOwner: oneTimeOwner.publicKey,
+        protocol: protocolPda,
+        pool: livePool,
+        poolVault: liveVault,
+        userTokenAccount: bettorAta,
+        bet: oneTimeBet,
+        referrerAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor, oneTimeOwner])
+      .rpc();
+
+    await program.methods
+      .placeBet(new anchor.BN(80), requestId)
+      .accountsPartial({
+        user: oneTimeOwner.publicKey,
+        pool: livePool,
+        bet: oneTimeBet,
+        poolAggregates: null,
+      })
+      .signers([oneTimeOwner])
+      .rpc();
+    liveBets.push(oneTimeBet);
+
+    const bet = await program.account.bet.fetch(oneTimeBet);
+    expect(bet.userPubkey.toBase58()).to.equal(oneTimeOwner.publicKey.toBase58());
+    console.log("    ✅ One-Time Key Bet Opened");
+  });
+
+  it("Opens a bet whose permission the bettor manages", async () => {
+    viewerBet = betPda(livePool, bettor.publicKey, viewerRequestId);
+    viewerPermission = permissionPdaFromAccount(viewerBet);
+
+    await program.methods
+      .initBet(BET_AMOUNT, viewerRequestId, null)
+      .accountsPartial({
+        user: bettor.publicKey,
+        betOwner: null,
+        protocol: protocolPda,
+        pool: livePool,
+        poolVault: liveVault,
+        userTokenAccount: bettorAta,
+        bet: viewerBet,
+        referrerAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .postInstructions([
+        await program.methods
+          .createBetPermission(viewerRequestId)
+          .accountsPartial({
+            payer: bettor.publicKey,
+            user: bettor.publicKey,
+            userBet: viewerBet,
+            pool: livePool,
+            permission: viewerPermission,
+            permissionProgram: PERMISSION_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction(),
+      ])
+      .signers([bettor])
+      .rpc();
+
+    await program.methods
+      .placeBet(new anchor.BN(76), viewerRequestId)
+      .accountsPartial({ user: bettor.publicKey, pool: livePool, bet: viewerBet, poolAggregates: null })
+      .signers([bettor])
+      .rpc();
+    liveBets.push(viewerBet);
+
+    expect(await provider.connection.getAccountInfo(viewerPermission)).to.not.be.null;
+    console.log("    ✅ Permissioned Bet Opened");
+  });
+
+  it("Bettor adds and removes a read-only viewer", async () => {
+    const viewerAccounts = {
+      user: bettor.publicKey,
+      bet: viewerBet,
+      permission: viewerPermission,
+      permissionProgram: PERMISSION_PROGRAM_ID,
+    };
+
+    await program.methods
+      .addBetViewer(viewerRequestId, viewer, TX_LOGS_FLAG | TX_BALANCES_FLAG)
+      .accountsPartial(viewerAccounts)
+      .signers([bettor])
+      .rpc();
+
+    try {
+      await program.methods
+        .addBetViewer(viewerRequestId, viewer, TX_LOGS_FLAG)
+        .accountsPartial(viewerAccounts)
+        .signers([bettor])
+        .rpc();
+      expect.fail("The same viewer was added twice");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidViewer");
+    }
+
+    await program.methods
+      .removeBetViewer(viewerRequestId, viewer)
+      .accountsPartial(viewerAccounts)
+      .signers([bettor])
+      .rpc();
+
+    try {
+      await program.methods
+        .removeBetViewer(viewerRequestId, viewer)
+        .accountsPartial(viewerAccounts)
+        .signers([bettor])
+        .rpc();
+      expect.fail("A viewer that is no longer a member was removed");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidViewer");
+    }
+    console.log("    ✅ Viewer Added And Removed");
+  });
+
+  it("Rejects viewers with authority and removing the bettor", async () => {
+    const viewerAccounts = {
+      user: bettor.publicKey,
+      bet: viewerBet,
+      permission: viewerPermission,
+      permissionProgram: PERMISSION_PROGRAM_ID,
+    };
+
+    try {
+      await program.methods
+        .addBetViewer(viewerRequestId, viewer, AUTHORITY_FLAG)
+        .accountsPartial(viewerAccounts)
+        .signers([bettor])
+        .rpc();
+      expect.fail("A viewer was given authority");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidViewer");
+    }
+
+    try {
+      await program.methods
+        .removeBetViewer(viewerRequestId, bettor.publicKey)
+        .accountsPartial(viewerAccounts)
+        .signers([bettor])
+        .rpc();
+      expect.fail("The bettor removed their own membership");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidViewer");
+    }
+    console.log("    ✅ Invalid Viewer Changes Rejected");
+  });
+
+  it("Rejects closing the permission of an unclaimed bet", async () => {
+    try {
+      await program.methods
+        .closeBetPermission(viewerRequestId)
+        .accountsPartial({
+          user: bettor.publicKey,
+          bet: viewerBet,
+          permission: viewerPermission,
+          permissionProgram: PERMISSION_PROGRAM_ID,
+        })
+        .signers([bettor])
+        .rpc();
+      expect.fail("Closed the permission of an unclaimed bet");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("BetNotClaimed");
+    }
+    console.log("    ✅ Early Permission Close Rejected");
+  });
+
+  it("Pins a validator before start and locks the pin once betting opens", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    [futurePool] = await createPool(now + 3600, now + 7200);
+    const [approvedValidator] = PublicKey.findProgramAddressSync(
+      [SEED_APPROVED_VALIDATOR, TEE_VALIDATOR.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .setPoolValidator()
+      .accountsPartial({
+        authority: admin.publicKey,
+        protocol: protocolPda,
+        pool: futurePool,
+        approvedValidator,
+        poolAggregates: null,
+      })
+      .rpc();
+    const pool = await program.account.pool.fetch(futurePool);
+    expect(pool.pinnedValidator.toBase58()).to.equal(TEE_VALIDATOR.toBase58());
+
+    try {
+      await program.methods
+        .setPoolValidator()
+        .accountsPartial({
+          authority: admin.publicKey,
+          protocol: protocolPda,
+          pool: livePool,
+          approvedValidator,
+          poolAggregates: null,
+        })
+        .rpc();
+      expect.fail("Changed the validator of a pool that is open for betting");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("PoolValidatorLocked");
+    }
+    console.log("    ✅ Validator Pin Locked After Start");
+  });
+
+  it("Rejects delegating a pool to a validator off the allow-list", async () => {
+    try {
+      await program.methods
+        .delegatePool()
+        .accountsPartial({
+          authority: admin.publicKey,
+          protocol: protocolPda,
+          pool: futurePool,
+          validator: Keypair.generate().publicKey,
+        })
+        .rpc();
+      expect.fail("Delegated a pool to an unapproved validator");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("AccountNotInitialized");
+    }
+    console.log("    ✅ Unapproved Validator Rejected");
+  });
+
+  it("Rejects pool delegation by anyone but the crank or the creator", async () => {
+    const outsider = Keypair.generate();
+    try {
+      await program.methods
+        .delegatePool()
+        .accountsPartial({
+          authority: outsider.publicKey,
+          protocol: protocolPda,
+          pool: futurePool,
+          validator: TEE_VALIDATOR,
+        })
+        .signers([outsider])
+        .rpc();
+      expect.fail("An outsider delegated a pool");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+    console.log("    ✅ Outsider Delegation Rejected");
+  });
+
+  it("Lets a non-admin creator delegate their own pool", async function () {
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    if (
+      !protocol.openPoolCreation ||
+      protocol.creatorWhitelistEnabled ||
+      !protocol.creatorBondAmount.isZero()
+    ) {
+      // Opening pool creation goes through the config timelock.
+      this.skip();
+    }
+
+    const poolId = protocol.totalPools.toNumber();
+    const [pool] = PublicKey.findProgramAddressSync(
+      [SEED_POOL, bettor.publicKey.toBuffer(), new anchor.BN(poolId).toBuffer("le", 8)],
+      program.programId
+    );
+    const [vault] = PublicKey.findProgramAddressSync(
+      [SEED_POOL_VAULT, pool.toBuffer()],
+      program.programId
+    );
+    const now = Math.floor(Date.now() / 1000);
+
+    await program.methods
+      .createPool(
+        new anchor.BN(poolId),
+        `Creator-${poolId}`,
+        new anchor.BN(now + 3600),
+        new anchor.BN(now + 7200),
+        new anchor.BN(10),
+        new anchor.BN(3),
+        null,
+        { onVolume: {} },
+        new anchor.BN(0),
+        OPEN_POLICY
+      )
+      .accountsPartial({
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        tokenMint: mint,
+        createdBy: bettor.publicKey,
+        createdByTokenAccount: bettorAta,
+        creatorWhitelist: null,
+        bondVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([bettor])
+      .rpc();
+
+    await program.methods
+      .delegatePool()
+      .accountsPartial({
+        authority: bettor.publicKey,
+        protocol: protocolPda,
+        pool,
+        validator: TEE_VALIDATOR,
+      })
+      .signers([bettor])
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(pool);
+    expect(info.owner.equals(program.programId)).to.be.false;
+    console.log("    ✅ Creator Delegated Own Pool");
+  });
+
+  it("Resolves the live pool", async () => {
+    await sleep(Math.max(0, liveEndTime * 1000 - Date.now() + 2000));
+
+    await program.methods
+      .resolvePool(new anchor.BN(75))
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: livePool })
+      .rpc();
+    console.log("    ✅ Pool Resolved");
+  });
+
+  it("Clears the prediction of a bet weighed inside the rollup", async () => {
+    await program.methods
+      .calculateWeightsInRollup()
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: livePool })
+      .remainingAccounts([{ pubkey: oneTimeBet, isWritable: true, isSigner: false }])
+      .rpc();
+
+    const bet = await program.account.bet.fetch(oneTimeBet);
+    expect(bet.status).to.deep.equal({ resolved: {} });
+    expect(bet.prediction.isZero()).to.be.true;
+    expect(bet.calculatedWeight.isZero()).to.be.false;
+    console.log("    ✅ Prediction Cleared After Weighing");
+  });
+
+  it("Weighs the bets and finalizes the live pool", async () => {
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: livePool })
+      .remainingAccounts(
+        liveBets.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+      )
+      .rpc();
+
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool: livePool,
+        poolVault: liveVault,
+        tokenMint: mint,
+        ...feeAccounts(),
+        creatorTokenAccount: adminAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(livePool);
+    expect(pool.weightFinalized).to.be.true;
+    console.log("    ✅ Pool Settled");
+  });
+
+  it("Rejects a relayed claim signed by the wrong key", async () => {
+    const forged = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: Keypair.generate().secretKey,
+      message: claimMessage(oneTimeBet, bettorAta),
+    });
+
+    try {
+      await program.methods
+        .claimReward()
+        .accountsPartial(claimAccounts(admin.publicKey, oneTimeBet))
+        .preInstructions([forged])
+        .rpc();
+      expect.fail("A claim signed by another key was accepted");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InvalidSignatureInstruction");
+    }
+    console.log("    ✅ Forged Claim Signature Rejected");
+  });
+
+  it("Relays a one-time-key claim carrying the owner's ed25519 signature", async () => {
+    const signature = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: oneTimeOwner.secretKey,
+      message: claimMessage(oneTimeBet, bettorAta),
+    });
+
+    await program.methods
+      .claimReward()
+      .accountsPartial(claimAccounts(admin.publicKey, oneTimeBet))
+      .preInstructions([signature])
+      .rpc();
+
+    const bet = await program.account.bet.fetch(oneTimeBet);
+    expect(bet.status).to.deep.equal({ claimed: {} });
+    console.log("    ✅ One-Time Key Claim Relayed");
+  });
+
+  it("Closes the permission of a claimed bet and returns its rent", async () => {
+    await program.methods
+      .claimReward()
+      .accountsPartial(claimAccounts(bettor.publicKey, viewerBet))
+      .signers([bettor])
+      .rpc();
+
+    const before = await provider.connection.getBalance(bettor.publicKey);
+    await program.methods
+      .closeBetPermission(viewerRequestId)
+      .accountsPartial({
+        user: bettor.publicKey,
+        bet: viewerBet,
+        permission: viewerPermission,
+        permissionProgram: PERMISSION_PROGRAM_ID,
+      })
+      .signers([bettor])
+      .rpc();
+    const after = await provider.connection.getBalance(bettor.publicKey);
+
+    expect(await provider.connection.getAccountInfo(viewerPermission)).to.be.null;
+    expect(after).to.be.greaterThan(before);
+    console.log("    ✅ Permission Closed");
+  });
+});
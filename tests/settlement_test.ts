@@ -0,0 +1,759 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  SEED_BET,
+  SEED_FEE_LEDGER,
+  SEED_FEE_VAULT,
+  SEED_POOL,
+  SEED_POOL_VAULT,
+  SEED_PROTOCOL,
+  SEED_REFERRER,
+  SEED_SPONSOR_CLAIM,
+  SEED_SPONSOR_REWARD,
+  SEED_SPONSOR_VAULT,
+  sleep,
+} from "./utils";
+
+const BET_AMOUNT = new anchor.BN(10 * 1e6);
+const OUTCOME = new anchor.BN(75);
+const SPONSOR_AMOUNT = new anchor.BN(6 * 1e6);
+
+interface PoolOptions {
+  startTime: number;
+  endTime: number;
+  feeBpsOverride?: anchor.BN;
+  feeMode?: object;
+  seedAmount?: anchor.BN;
+}
+
+describe("4. Settlement & Fees", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const winner = Keypair.generate();
+  const loser = Keypair.generate();
+
+  let mint: PublicKey;
+  let adminAta: PublicKey;
+  let winnerAta: PublicKey;
+  let loserAta: PublicKey;
+  let protocolPda: PublicKey;
+
+  let settledPool: PublicKey;
+  let settledVault: PublicKey;
+  let settledEndTime: number;
+  let winnerBet: PublicKey;
+  let loserBet: PublicKey;
+  let referredBet: PublicKey;
+  let referrerAccount: PublicKey;
+  let creatorFeePaid: anchor.BN;
+  let rewardMint: PublicKey;
+  let sponsorReward: PublicKey;
+  let sponsorVault: PublicKey;
+
+  function poolPdas(poolId: number): [PublicKey, PublicKey] {
+    const [pool] = PublicKey.findProgramAddressSync(
+      [SEED_POOL, admin.publicKey.toBuffer(), new anchor.BN(poolId).toBuffer("le", 8)],
+      program.programId
+    );
+    const [vault] = PublicKey.findProgramAddressSync(
+      [SEED_POOL_VAULT, pool.toBuffer()],
+      program.programId
+    );
+    return [pool, vault];
+  }
+
+  function betPda(pool: PublicKey, owner: PublicKey, requestId: string): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [SEED_BET, pool.toBuffer(), owner.toBuffer(), Buffer.from(requestId)],
+      program.programId
+    )[0];
+  }
+
+  function feeAccounts() {
+    const [feeLedger] = PublicKey.findProgramAddressSync(
+      [SEED_FEE_LEDGER, mint.toBuffer()],
+      program.programId
+    );
+    const [feeVault] = PublicKey.findProgramAddressSync(
+      [SEED_FEE_VAULT, mint.toBuffer()],
+      program.programId
+    );
+    return { feeLedger, feeVault };
+  }
+
+  function referrerPda(referrer: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [SEED_REFERRER, referrer.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function createPool(options: PoolOptions): Promise<[PublicKey, PublicKey]> {
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    const poolId = protocol.totalPools.toNumber();
+    const [pool, vault] = poolPdas(poolId);
+
+    await program.methods
+      .createPool(
+        new anchor.BN(poolId),
+        `Settlement-${poolId}`,
+        new anchor.BN(options.startTime),
+        new anchor.BN(options.endTime),
+        new anchor.BN(10),
+        new anchor.BN(3),
+        options.feeBpsOverride ?? null,
+        (options.feeMode ?? { onVolume: {} }) as any,
+        options.seedAmount ?? new anchor.BN(0),
+        { strict: false, hideStakes: false }
+      )
+      .accountsPartial({
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        tokenMint: mint,
+        createdBy: admin.publicKey,
+        createdByTokenAccount: adminAta,
+        creatorWhitelist: null,
+        bondVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    return [pool, vault];
+  }
+
+  async function openBet(
+    user: Keypair,
+    userAta: PublicKey,
+    pool: PublicKey,
+    vault: PublicKey,
+    requestId: string,
+    prediction: anchor.BN,
+    referrer: PublicKey | null = null
+  ): Promise<PublicKey> {
+    const bet = betPda(pool, user.publicKey, requestId);
+
+    await program.methods
+      .initBet(BET_AMOUNT, requestId, referrer)
+      .accountsPartial({
+        user: user.publicKey,
+        betOwner: null,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        userTokenAccount: userAta,
+        bet,
+        referrerAccount: referrer && referrerPda(referrer),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .placeBet(prediction, requestId)
+      .accountsPartial({ user: user.publicKey, pool, bet, poolAggregates: null })
+      .signers([user])
+      .rpc();
+    return bet;
+  }
+
+  async function settle(pool: PublicKey, vault: PublicKey, bets: PublicKey[]) {
+    await program.methods
+      .resolvePool(OUTCOME)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool })
+      .rpc();
+
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool })
+      .remainingAccounts(bets.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .rpc();
+
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        tokenMint: mint,
+        ...feeAccounts(),
+        creatorTokenAccount: adminAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  function claimAccounts(
+    user: PublicKey,
+    userAta: PublicKey,
+    pool: PublicKey,
+    vault: PublicKey,
+    bet: PublicKey
+  ) {
+    return {
+      user,
+      protocol: protocolPda,
+      pool,
+      poolVault: vault,
+      bet,
+      userTokenAccount: userAta,
+      recipient: null,
+      referrerAccount: null,
+      ...feeAccounts(),
+      creatorTokenAccount: adminAta,
+      instructionsSysvar: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  function refundAccounts(
+    user: Keypair,
+    userAta: PublicKey,
+    pool: PublicKey,
+    vault: PublicKey,
+    bet: PublicKey
+  ) {
+    return {
+      user: user.publicKey,
+      protocol: protocolPda,
+      bet,
+      pool,
+      poolVault: vault,
+      userTokenAccount: userAta,
+      recipient: null,
+      instructionsSysvar: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  async function tokenBalance(account: PublicKey): Promise<anchor.BN> {
+    return new anchor.BN((await getAccount(provider.connection, account)).amount.toString());
+  }
+
+  before(async () => {
+    [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+    const funding = new anchor.web3.Transaction();
+    for (const user of [winner, loser]) {
+      funding.add(
+        SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 0.1 * LAMPORTS_PER_SOL,
+        })
+      );
+    }
+    await provider.sendAndConfirm(funding);
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    adminAta = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, admin.publicKey)
+    ).address;
+    winnerAta = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, winner.publicKey)
+    ).address;
+    loserAta = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, loser.publicKey)
+    ).address;
+    for (const ata of [adminAta, winnerAta, loserAta]) {
+      await mintTo(provider.connection, admin, mint, ata, admin, 100 * 1e6);
+    }
+  });
+
+  it("Opens a pool and takes a winning and a losing bet", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    settledEndTime = now + 30;
+    [settledPool, settledVault] = await createPool({ startTime: now, endTime: settledEndTime });
+
+    winnerBet = await openBet(winner, winnerAta, settledPool, settledVault, "winner", OUTCOME);
+    loserBet = await openBet(loser, loserAta, settledPool, settledVault, "loser", new anchor.BN(1000));
+
+    const pool = await program.account.pool.fetch(settledPool);
+    expect(pool.totalVolume.toString()).to.equal(BET_AMOUNT.muln(2).toString());
+    console.log("    ✅ Bets Opened");
+  });
+
+  it("Tracks a losing bet placed through a referrer", async () => {
+    referrerAccount = referrerPda(winner.publicKey);
+    referredBet = await openBet(
+      loser,
+      loserAta,
+      settledPool,
+      settledVault,
+      "referred",
+      new anchor.BN(1000),
+      winner.publicKey
+    );
+
+    const referrer = await program.account.referrerAccount.fetch(referrerAccount);
+    expect(referrer.referredBets.toNumber()).to.equal(1);
+    expect(referrer.referredVolume.toString()).to.equal(BET_AMOUNT.toString());
+    console.log("    ✅ Referral Tracked");
+  });
+
+  it("Sponsors the open pool with a reward in a second mint", async () => {
+    rewardMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const sponsorAta = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin, rewardMint, admin.publicKey)
+    ).address;
+    await mintTo(provider.connection, admin, rewardMint, sponsorAta, admin, SPONSOR_AMOUNT.toNumber());
+
+    [sponsorReward] = PublicKey.findProgramAddressSync(
+      [SEED_SPONSOR_REWARD, settledPool.toBuffer(), rewardMint.toBuffer(), admin.publicKey.toBuffer()],
+      program.programId
+    );
+    [sponsorVault] = PublicKey.findProgramAddressSync(
+      [SEED_SPONSOR_VAULT, sponsorReward.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .sponsorPool(SPONSOR_AMOUNT)
+      .accountsPartial({
+        sponsor: admin.publicKey,
+        pool: settledPool,
+        rewardMint,
+        sponsorReward,
+        sponsorVault,
+        sponsorTokenAccount: sponsorAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    expect((await tokenBalance(sponsorVault)).toString()).to.equal(SPONSOR_AMOUNT.toString());
+    console.log("    ✅ Pool Sponsored");
+  });
+
+  it("Resolves the pool and finalizes weights", async () => {
+    await sleep(Math.max(0, settledEndTime * 1000 - Date.now() + 2000));
+    const creatorBefore = await tokenBalance(adminAta);
+    await settle(settledPool, settledVault, [winnerBet, loserBet, referredBet]);
+    creatorFeePaid = (await tokenBalance(adminAta)).sub(creatorBefore);
+
+    const pool = await program.account.pool.fetch(settledPool);
+    expect(pool.weightFinalized).to.be.true;
+    const bet = await program.account.bet.fetch(loserBet);
+    expect(bet.calculatedWeight.isZero()).to.be.true;
+    console.log("    ✅ Pool Settled");
+  });
+
+  it("Accrues the protocol fee in the fee vault", async () => {
+    const { feeLedger, feeVault } = feeAccounts();
+    const ledger = await program.account.feeLedger.fetch(feeLedger);
+    const pool = await program.account.pool.fetch(settledPool);
+
+    const expectedFee = pool.totalVolume.mul(pool.feeBps).divn(10_000);
+    expect(ledger.totalAccrued.lte(expectedFee)).to.be.true;
+    expect((await tokenBalance(feeVault)).toString()).to.equal(ledger.totalAccrued.toString());
+    console.log("    ✅ Fee Held In Fee Vault");
+  });
+
+  it("Pays the pool creator its share of the fee at finalization", async () => {
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    const pool = await program.account.pool.fetch(settledPool);
+
+    const fee = pool.totalVolume.mul(pool.feeBps).divn(10_000);
+    const creatorFee = fee.mul(protocol.creatorFeeShareBps).divn(10_000);
+    expect(creatorFeePaid.toString()).to.equal(creatorFee.toString());
+    console.log("    ✅ Creator Fee Share Paid");
+  });
+
+  it("Shares the sponsor reward among weighted bets only", async () => {
+    async function claimSponsorReward(user: Keypair, bet: PublicKey): Promise<PublicKey> {
+      const rewardAta = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, admin, rewardMint, user.publicKey)
+      ).address;
+      const [sponsorClaim] = PublicKey.findProgramAddressSync(
+        [SEED_SPONSOR_CLAIM, sponsorReward.toBuffer(), bet.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .claimSponsorReward()
+        .accountsPartial({
+          user: user.publicKey,
+          protocol: protocolPda,
+          pool: settledPool,
+          bet,
+          sponsorReward,
+          sponsorVault,
+          sponsorClaim,
+          userTokenAccount: rewardAta,
+          instructionsSysvar: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+      return rewardAta;
+    }
+
+    // The winner holds all of the pool's weight.
+    const winnerRewardAta = await claimSponsorReward(winner, winnerBet);
+    expect((await tokenBalance(winnerRewardAta)).toString()).to.equal(SPONSOR_AMOUNT.toString());
+
+    try {
+      await claimSponsorReward(loser, loserBet);
+      expect.fail("A bet without weight claimed a sponsor reward");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("NoWinningWeight");
+    }
+
+    try {
+      await program.methods
+        .refundSponsorReward()
+        .accountsPartial({
+          sponsor: admin.publicKey,
+          pool: settledPool,
+          sponsorReward,
+          sponsorVault,
+          sponsorTokenAccount: (
+            await getOrCreateAssociatedTokenAccount(provider.connection, admin, rewardMint, admin.publicKey)
+          ).address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("A sponsor reward with winners was refunded");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("SponsorRewardNotRefundable");
+    }
+    console.log("    ✅ Sponsor Reward Shared By Weight");
+  });
+
+  it("Credits the held-back referral share of a losing bet to its referrer", async () => {
+    await program.methods
+      .creditReferralFees()
+      .accountsPartial({
+        payer: admin.publicKey,
+        protocol: protocolPda,
+        pool: settledPool,
+        poolVault: settledVault,
+        feeVault: feeAccounts().feeVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        [referredBet, referrerAccount].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+      )
+      .rpc();
+
+    const pool = await program.account.pool.fetch(settledPool);
+    const share = pool.referralFees.mul(BET_AMOUNT).div(pool.referredVolume);
+    expect((await program.account.bet.fetch(referredBet)).referralCredited).to.be.true;
+    const referrer = await program.account.referrerAccount.fetch(referrerAccount);
+    expect(referrer.totalEarned.toString()).to.equal(share.toString());
+
+    const claimAccounts = {
+      referrer: winner.publicKey,
+      protocol: protocolPda,
+      referrerAccount,
+      feeVault: feeAccounts().feeVault,
+      referrerTokenAccount: winnerAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    if (share.isZero()) {
+      // No referrer fee share is configured on this protocol.
+      try {
+        await program.methods
+          .claimReferralRewards()
+          .accountsPartial(claimAccounts)
+          .signers([winner])
+          .rpc();
+        expect.fail("Claimed referral rewards that were never earned");
+      } catch (e) {
+        expect(e.error?.errorCode?.code).to.equal("NoReferralRewards");
+      }
+    } else {
+      const before = await tokenBalance(winnerAta);
+      await program.methods
+        .claimReferralRewards()
+        .accountsPartial(claimAccounts)
+        .signers([winner])
+        .rpc();
+      expect((await tokenBalance(winnerAta)).sub(before).toString()).to.equal(share.toString());
+    }
+    console.log("    ✅ Referral Share Credited");
+  });
+
+  it("Rejects withdrawing more fees than the ledger holds", async () => {
+    const { feeLedger, feeVault } = feeAccounts();
+    const ledger = await program.account.feeLedger.fetch(feeLedger);
+
+    try {
+      await program.methods
+        .withdrawFees(ledger.totalAccrued.addn(1))
+        .accountsPartial({
+          admin: admin.publicKey,
+          protocol: protocolPda,
+          feeLedger,
+          feeVault,
+          treasuryTokenAccount: adminAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Withdrew more than the accrued fees");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("InsufficientFees");
+    }
+    console.log("    ✅ Overdrawn Fee Withdrawal Rejected");
+  });
+
+  it("Snapshots a per-pool fee override and caps it", async () => {
+    const now = Math.floor(Date.now() / 1000);
+
+    try {
+      await createPool({
+        startTime: now + 3600,
+        endTime: now + 7200,
+        feeBpsOverride: new anchor.BN(1_001),
+      });
+      expect.fail("A fee override above MAX_PROTOCOL_FEE_BPS was accepted");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("FeeTooHigh");
+    }
+
+    const [pool] = await createPool({
+      startTime: now + 3600,
+      endTime: now + 7200,
+      feeBpsOverride: new anchor.BN(50),
+    });
+    expect((await program.account.pool.fetch(pool)).feeBps.toNumber()).to.equal(50);
+    console.log("    ✅ Fee Override Applied And Capped");
+  });
+
+  it("Charges profit-fee pools only on the winnings, at claim time", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const endTime = now + 30;
+    const [pool, vault] = await createPool({
+      startTime: now,
+      endTime,
+      feeMode: { onProfit: {} },
+    });
+    const profitWinner = await openBet(winner, winnerAta, pool, vault, "profit_winner", OUTCOME);
+    const profitLoser = await openBet(loser, loserAta, pool, vault, "profit_loser", new anchor.BN(1000));
+
+    await sleep(Math.max(0, endTime * 1000 - Date.now() + 2000));
+    const { feeVault } = feeAccounts();
+    const feesBefore = await tokenBalance(feeVault);
+    await settle(pool, vault, [profitWinner, profitLoser]);
+    expect((await tokenBalance(feeVault)).toString()).to.equal(feesBefore.toString());
+
+    const winnerBefore = await tokenBalance(winnerAta);
+    await program.methods
+      .claimReward()
+      .accountsPartial(claimAccounts(winner.publicKey, winnerAta, pool, vault, profitWinner))
+      .signers([winner])
+      .rpc();
+    const received = (await tokenBalance(winnerAta)).sub(winnerBefore);
+
+    const feeBps = (await program.account.pool.fetch(pool)).feeBps;
+    const pot = BET_AMOUNT.muln(2);
+    const fee = pot.sub(BET_AMOUNT).mul(feeBps).divn(10_000);
+    expect(received.toString()).to.equal(pot.sub(fee).toString());
+    console.log("    ✅ Fee Charged On Winnings Only");
+  });
+
+  it("Rejects settlement steps from accounts without the resolver or crank role", async () => {
+    const outsider = Keypair.generate();
+
+    try {
+      await program.methods
+        .resolvePool(OUTCOME)
+        .accountsPartial({ admin: outsider.publicKey, protocol: protocolPda, pool: settledPool })
+        .signers([outsider])
+        .rpc();
+      expect.fail("An outsider resolved a pool");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+
+    try {
+      await program.methods
+        .batchCalculateWeights()
+        .accountsPartial({ admin: outsider.publicKey, protocol: protocolPda, pool: settledPool })
+        .remainingAccounts([{ pubkey: winnerBet, isWritable: true, isSigner: false }])
+        .signers([outsider])
+        .rpc();
+      expect.fail("An outsider ran the weight crank");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+    console.log("    ✅ Settlement Is Role-Gated");
+  });
+
+  it("Freezing a pool stops new bets until it is unfrozen", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const [pool, vault] = await createPool({ startTime: now, endTime: now + 3600 });
+    const freezeAccounts = { admin: admin.publicKey, protocol: protocolPda, pool };
+
+    await program.methods.setPoolFreeze(true).accountsPartial(freezeAccounts).rpc();
+    try {
+      await openBet(winner, winnerAta, pool, vault, "frozen", OUTCOME);
+      expect.fail("A bet was placed on a frozen pool");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("PoolFrozen");
+    }
+
+    await program.methods.setPoolFreeze(false).accountsPartial(freezeAccounts).rpc();
+    await openBet(winner, winnerAta, pool, vault, "unfrozen", OUTCOME);
+    console.log("    ✅ Pool Freeze Enforced");
+  });
+
+  it("Refunds bets once their pool is cancelled", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const [pool, vault] = await createPool({ startTime: now, endTime: now + 3600 });
+    const bet = await openBet(loser, loserAta, pool, vault, "cancelled", OUTCOME);
+    const accounts = refundAccounts(loser, loserAta, pool, vault, bet);
+
+    try {
+      await program.methods.emergencyRefund().accountsPartial(accounts).signers([loser]).rpc();
+      expect.fail("A bet was refunded from an open pool");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("TimeoutNotMet");
+    }
+
+    await program.methods
+      .cancelPool(false)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool })
+      .rpc();
+
+    const before = await tokenBalance(loserAta);
+    await program.methods.emergencyRefund().accountsPartial(accounts).signers([loser]).rpc();
+    const after = await tokenBalance(loserAta);
+
+    expect(after.sub(before).toString()).to.equal(BET_AMOUNT.toString());
+    expect((await program.account.bet.fetch(bet)).status).to.deep.equal({ claimed: {} });
+    console.log("    ✅ Cancelled Pool Refunded");
+  });
+
+  it("Rejects refunds once the pool is resolved", async () => {
+    try {
+      await program.methods
+        .emergencyRefund()
+        .accountsPartial(refundAccounts(loser, loserAta, settledPool, settledVault, loserBet))
+        .signers([loser])
+        .rpc();
+      expect.fail("A bet of a resolved pool was refunded");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("PoolAlreadyResolved");
+    }
+    console.log("    ✅ Refund After Resolution Rejected");
+  });
+
+  it("Holds community creators to the pool creation policy", async () => {
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    const poolId = protocol.totalPools.toNumber();
+    const [pool] = PublicKey.findProgramAddressSync(
+      [SEED_POOL, winner.publicKey.toBuffer(), new anchor.BN(poolId).toBuffer("le", 8)],
+      program.programId
+    );
+    const [vault] = PublicKey.findProgramAddressSync(
+      [SEED_POOL_VAULT, pool.toBuffer()],
+      program.programId
+    );
+
+    let expected = "Unauthorized";
+    if (!protocol.openPoolCreation) {
+      expected = "PoolCreationClosed";
+    } else if (protocol.creatorWhitelistEnabled) {
+      expected = "CreatorNotWhitelisted";
+    }
+
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods
+        .createPool(
+          new anchor.BN(poolId),
+          `Community-${poolId}`,
+          new anchor.BN(now + 3600),
+          new anchor.BN(now + 7200),
+          new anchor.BN(10),
+          new anchor.BN(3),
+          // Only pool creator role holders may override the fee.
+          new anchor.BN(0),
+          { onVolume: {} },
+          new anchor.BN(0),
+          { strict: false, hideStakes: false }
+        )
+        .accountsPartial({
+          protocol: protocolPda,
+          pool,
+          poolVault: vault,
+          tokenMint: mint,
+          createdBy: winner.publicKey,
+          createdByTokenAccount: winnerAta,
+          creatorWhitelist: null,
+          bondVault: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([winner])
+        .rpc();
+      expect.fail("A community pool bypassed the creation policy");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal(expected);
+    }
+    console.log("    ✅ Pool Creation Policy Enforced");
+  });
+
+  it("Returns the seed subsidy to the creator once the pool is cancelled", async () => {
+    const seedAmount = new anchor.BN(5 * 1e6);
+    const now = Math.floor(Date.now() / 1000);
+    const [pool, vault] = await createPool({
+      startTime: now,
+      endTime: now + 3600,
+      seedAmount,
+    });
+    const reclaimAccounts = {
+      creator: admin.publicKey,
+      pool,
+      poolVault: vault,
+      creatorTokenAccount: adminAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    try {
+      await program.methods.reclaimSeedSubsidy().accountsPartial(reclaimAccounts).rpc();
+      expect.fail("The subsidy of an open pool was reclaimed");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("SubsidyNotReclaimable");
+    }
+
+    await program.methods
+      .cancelPool(false)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool })
+      .rpc();
+
+    const before = await tokenBalance(adminAta);
+    await program.methods.reclaimSeedSubsidy().accountsPartial(reclaimAccounts).rpc();
+    const after = await tokenBalance(adminAta);
+    expect(after.sub(before).toString()).to.equal(seedAmount.toString());
+
+    try {
+      await program.methods.reclaimSeedSubsidy().accountsPartial(reclaimAccounts).rpc();
+      expect.fail("The subsidy was reclaimed twice");
+    } catch (e) {
+      expect(e.error?.errorCode?.code).to.equal("SubsidyNotReclaimable");
+    }
+    console.log("    ✅ Seed Subsidy Reclaimed");
+  });
+});
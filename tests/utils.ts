@@ -3,7 +3,19 @@ import { PublicKey } from "@solana/web3.js";
 export const SEED_BET = Buffer.from("bet");
 export const SEED_POOL = Buffer.from("pool");
 export const SEED_POOL_VAULT = Buffer.from("pool_vault");
+export const SEED_POOL_AGGREGATES = Buffer.from("pool_aggregates");
 export const SEED_PROTOCOL = Buffer.from("protocol_v1");
+export const SEED_FEE_VAULT = Buffer.from("fee_vault");
+export const SEED_FEE_LEDGER = Buffer.from("fee_ledger");
+export const SEED_CONFIG_CHANGE = Buffer.from("config_change");
+export const SEED_DEPOSIT_POOL = Buffer.from("deposit_pool");
+export const SEED_DEPOSIT_VAULT = Buffer.from("deposit_vault");
+export const SEED_SHIELDED_BALANCE = Buffer.from("shielded_balance");
+export const SEED_SPONSOR_REWARD = Buffer.from("sponsor_reward");
+export const SEED_SPONSOR_VAULT = Buffer.from("sponsor_vault");
+export const SEED_SPONSOR_CLAIM = Buffer.from("sponsor_claim");
+export const SEED_REFERRER = Buffer.from("referrer");
+export const SEED_APPROVED_VALIDATOR = Buffer.from("approved_validator");
 
 export const TEE_VALIDATOR = new PublicKey("FnE6VJT5QNZdedZPnCoLsARgBwoE6DeJNjBs2H1gySXA");
 